@@ -0,0 +1,106 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use progressing::{
+    bernoulli::Bar as BernoulliBar, clamping::Bar as ClampingBar, mapping::Bar as MappingBar,
+    Baring,
+};
+use std::fmt::Write;
+
+/// Renders a bar like before rendering became non-allocating, i.e. by repeating strings and
+/// formatting the approximated time into its own `String`.
+mod allocating {
+    use std::cmp::min;
+
+    pub fn clamped(progress: f64, bar_len: usize) -> String {
+        let inner_bar_len = bar_len - 2;
+        let reached = (progress * inner_bar_len as f64) as usize;
+
+        let line = "=".repeat(reached);
+        let hat = &">"[0..min(1, inner_bar_len - reached)];
+        let empty_line = "-".repeat(inner_bar_len - reached - hat.len());
+        format!("{}{}{}{}{}", "[", line, hat, empty_line, "]")
+    }
+
+    pub fn mapped(k: usize, end: usize, bar_len: usize) -> String {
+        format!(
+            "{} ({}/{})",
+            clamped(k as f64 / end as f64, bar_len),
+            k,
+            end
+        )
+    }
+
+    pub fn timed(k: usize, end: usize, bar_len: usize, elapsed_s: f64) -> String {
+        let scale = (end - k) as f64 / k as f64;
+        let approx_time = format!("{}{}", (elapsed_s * scale) as usize, "s");
+        format!("{} ~{}", mapped(k, end, bar_len), approx_time)
+    }
+}
+
+fn clamping(c: &mut Criterion) {
+    let mut progress_bar = ClampingBar::new();
+    progress_bar.set_len(80);
+    progress_bar.set(0.42);
+
+    let mut group = c.benchmark_group("clamping");
+    group.bench_function("allocating (old)", |b| {
+        b.iter(|| allocating::clamped(black_box(0.42), black_box(80)))
+    });
+    group.bench_function("to_string", |b| {
+        b.iter(|| black_box(&progress_bar).to_string())
+    });
+    let mut buffer = String::new();
+    group.bench_function("reused buffer", |b| {
+        b.iter(|| {
+            buffer.clear();
+            write!(buffer, "{}", black_box(&progress_bar)).unwrap();
+        })
+    });
+    group.finish();
+}
+
+fn mapping(c: &mut Criterion) {
+    let mut progress_bar = MappingBar::with_range(0usize, 1_000).timed();
+    progress_bar.set_len(80);
+    progress_bar.set_prefix("downloading");
+    progress_bar.set_message("file_42.txt");
+    progress_bar.set(420usize);
+
+    let mut group = c.benchmark_group("mapping timed");
+    group.bench_function("allocating (old)", |b| {
+        b.iter(|| {
+            let line = allocating::timed(black_box(420), black_box(1_000), 80, 12.3);
+            format!("{} {} {}", "downloading", line, "file_42.txt")
+        })
+    });
+    group.bench_function("to_string", |b| {
+        b.iter(|| black_box(&progress_bar).to_string())
+    });
+    let mut buffer = String::new();
+    group.bench_function("reused buffer", |b| {
+        b.iter(|| {
+            buffer.clear();
+            write!(buffer, "{}", black_box(&progress_bar)).unwrap();
+        })
+    });
+    group.finish();
+}
+
+fn bernoulli(c: &mut Criterion) {
+    let mut progress_bar = BernoulliBar::with_goal(60);
+    progress_bar.set_len(80);
+    progress_bar.set((42, 130));
+
+    let mut group = c.benchmark_group("bernoulli");
+    let mut buffer = Vec::new();
+    group.bench_function("reused byte buffer", |b| {
+        b.iter(|| {
+            buffer.clear();
+            std::io::Write::write_fmt(&mut buffer, format_args!("{}", black_box(&progress_bar)))
+                .unwrap();
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, clamping, mapping, bernoulli);
+criterion_main!(benches);
@@ -0,0 +1,47 @@
+use progressing::{mapping::Bar as MappingBar, snapshot::Snapshotting, Baring};
+use std::{env, io, thread, time};
+
+const SLEEP_MS: u64 = 10;
+
+fn main() -> io::Result<()> {
+    let path = env::temp_dir().join("progressing-example-checkpoint.txt");
+
+    // the job is interrupted at 40 %
+    println!("Running the job until it is interrupted");
+    let mut progress_bar = MappingBar::with_range(0usize, 100).timed();
+    progress_bar.set_len(20);
+    run(&mut progress_bar, 0..40);
+    progress_bar.checkpoint(&path)?;
+    progress_bar.abandon();
+    println!("{}", progress_bar);
+
+    // the restarted job continues where it has been interrupted
+    // and its time-approximation accounts for the time already spent
+    println!("Resuming the job");
+    let mut progress_bar = MappingBar::with_range(0usize, 100).timed();
+    progress_bar.set_len(20);
+    progress_bar.resume(&path)?;
+    println!("{}", progress_bar);
+    run(&mut progress_bar, 40..=100);
+    progress_bar.finish();
+    println!("{}", progress_bar);
+
+    std::fs::remove_file(&path)
+}
+
+fn run<B, I>(progress_bar: &mut B, values: I)
+where
+    B: Baring<Progress = usize> + std::fmt::Display,
+    I: Iterator<Item = usize>,
+{
+    for value in values {
+        progress_bar.set(value);
+        if progress_bar.has_progressed_significantly() {
+            progress_bar.remember_significant_progress();
+            println!("{}", progress_bar);
+        }
+
+        // sleep for visual effects ;)
+        thread::sleep(time::Duration::from_millis(SLEEP_MS));
+    }
+}
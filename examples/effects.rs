@@ -0,0 +1,27 @@
+use progressing::{
+    clamping::{Bar as ClampingBar, Effect},
+    printing::Printer,
+    Baring,
+};
+use std::{thread, time::Duration};
+
+/// Animating every colored effect, which falls back to the plain style without colors
+fn main() {
+    println!("Animating every colored effect");
+    let mut printer = Printer::stderr();
+    for &effect in &[Effect::Gradient, Effect::Shimmer, Effect::Pulse] {
+        let mut progress_bar = ClampingBar::new();
+        progress_bar.set_len(30);
+        progress_bar.set_prefix(format!("{:<8}", format!("{:?}", effect)));
+        progress_bar.set_effect(effect);
+
+        for i in 0..=30 {
+            progress_bar.set(i as f64 / 40.0);
+            progress_bar.tick();
+            printer.print(&mut progress_bar).unwrap();
+            thread::sleep(Duration::from_millis(15));
+        }
+        progress_bar.finish();
+        printer.print(&mut progress_bar).unwrap();
+    }
+}
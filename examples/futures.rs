@@ -0,0 +1,68 @@
+use futures_util::{stream, StreamExt};
+use progressing::{
+    bernoulli::Bar as BernoulliBar,
+    futures::{Attempts, ProgressStreamExt},
+    mapping::Bar as MappingBar,
+    printing::Printer,
+    ticking::Ticker,
+    Baring,
+};
+use std::{
+    sync::{Arc, Mutex},
+    thread, time,
+};
+
+const SLEEP_MS: u64 = 10;
+
+fn main() {
+    let printer = Printer::stdout().shared();
+
+    // a stream driving a bar, which is printed by a ticker
+    let progress_bar = Arc::new(Mutex::new(MappingBar::with_range(0usize, 50).timed()));
+    progress_bar.lock().unwrap().set_len(20);
+    let ticker = Ticker::spawn(
+        Arc::clone(&progress_bar),
+        Arc::clone(&printer),
+        time::Duration::from_millis(SLEEP_MS),
+    );
+    let items = stream::iter(0..50)
+        .map(|i| {
+            // sleep for visual effects ;)
+            thread::sleep(time::Duration::from_millis(SLEEP_MS));
+            i
+        })
+        .progress(Arc::clone(&progress_bar));
+    let sum = futures_executor::block_on(items.fold(0, |sum, i| async move { sum + i }));
+    ticker.stop();
+    printer
+        .lock()
+        .unwrap()
+        .println(format!("Sum is {}", sum))
+        .unwrap();
+
+    // futures counted as successes (Ok) or failed attempts (Err)
+    let progress_bar = Arc::new(Mutex::new(BernoulliBar::with_goal(40).timed()));
+    progress_bar.lock().unwrap().set_len(20);
+    let ticker = Ticker::spawn(
+        Arc::clone(&progress_bar),
+        Arc::clone(&printer),
+        time::Duration::from_millis(SLEEP_MS),
+    );
+    let jobs = (0..60).map(|i| async move {
+        thread::sleep(time::Duration::from_millis(SLEEP_MS));
+        if i % 3 == 0 {
+            Err(i)
+        } else {
+            Ok(i)
+        }
+    });
+    let outputs: Vec<_> =
+        futures_executor::block_on(Attempts::with(jobs, Arc::clone(&progress_bar)).collect());
+    ticker.stop();
+    let failures = outputs.iter().filter(|output| output.is_err()).count();
+    printer
+        .lock()
+        .unwrap()
+        .println(format!("{} jobs failed", failures))
+        .unwrap();
+}
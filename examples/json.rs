@@ -0,0 +1,33 @@
+use progressing::{
+    bernoulli::Bar as BernoulliBar,
+    json::{Reader, Writer},
+    snapshot::Snapshotting,
+    Baring,
+};
+use std::io;
+
+fn main() -> io::Result<()> {
+    // e.g. a child-process writing its progress to stdout
+    let mut writer = Writer::with(Vec::new());
+    let mut progress_bar = BernoulliBar::with_goal(60).timed();
+    for value in 0..100 {
+        // job is successful if value is even
+        progress_bar.add(value % 2 == 0);
+        writer.write("evens", &progress_bar)?;
+    }
+    progress_bar.finish();
+    writer.write("evens", &progress_bar)?;
+
+    let stream = writer.into_inner();
+    print!("{}", String::from_utf8_lossy(&stream));
+
+    // e.g. the parent-process reconstructing the bar
+    let mut reconstructed = BernoulliBar::with_goal(60).timed();
+    reconstructed.set_len(20);
+    for event in Reader::with(stream.as_slice()) {
+        reconstructed.restore(&event?.snapshot());
+    }
+    println!("{}", reconstructed);
+
+    Ok(())
+}
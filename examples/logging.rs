@@ -0,0 +1,37 @@
+use log::{info, warn, LevelFilter};
+use progressing::{logging::Logger, mapping::Bar as MappingBar, printing::Printer, Baring};
+use std::{io, thread, time};
+
+const SLEEP_MS: u64 = 20;
+
+fn main() -> io::Result<()> {
+    // the logger prints records above the printer's bar
+    let printer = Printer::stderr().shared();
+    if Logger::new(printer.clone())
+        .install(LevelFilter::Info)
+        .is_err()
+    {
+        eprintln!("Another logger has already been installed.");
+    }
+
+    let mut progress_bar = MappingBar::with_range(0, 100).timed();
+    progress_bar.set_len(20);
+
+    for value in 0..=100 {
+        progress_bar.set(value);
+        printer.lock().unwrap().print(&mut progress_bar)?;
+
+        if value % 25 == 0 {
+            info!("Reached {}", value);
+        }
+        if value == 42 {
+            warn!("Found the answer");
+        }
+
+        // sleep for visual effects ;)
+        thread::sleep(time::Duration::from_millis(SLEEP_MS));
+    }
+    progress_bar.finish();
+    let mut printer = printer.lock().unwrap();
+    printer.print(&mut progress_bar)
+}
@@ -15,6 +15,7 @@ fn main() {
     bernoulli();
 }
 
+#[allow(clippy::print_literal)]
 fn clamped() {
     let min_value = -80;
     let max_value = 180;
@@ -43,7 +44,8 @@ fn clamped() {
         // sleep for visual effects ;)
         thread::sleep(time::Duration::from_millis(SLEEP_MS));
     }
-    // add new line to finished progress-bar
+    // print the final frame
+    progress_bar.finish();
     println!("{}", progress_bar);
 }
 
@@ -73,7 +75,8 @@ fn mapped() {
         // sleep for visual effects ;)
         thread::sleep(time::Duration::from_millis(SLEEP_MS));
     }
-    // add new line to finished progress-bar
+    // print the final frame
+    progress_bar.finish();
     println!("{}", progress_bar);
 }
 
@@ -107,6 +110,7 @@ fn bernoulli() {
         // sleep for visual effects ;)
         thread::sleep(time::Duration::from_millis(SLEEP_MS));
     }
-    // add new line to finished progress-bar
+    // print the final frame
+    progress_bar.finish();
     println!("{}", progress_bar);
 }
@@ -0,0 +1,51 @@
+use progressing::{
+    bernoulli::Bar as BernoulliBar,
+    mapping::Bar as MappingBar,
+    nesting::{Bar as NestingBar, Layout},
+    printing::Printer,
+    Baring,
+};
+use std::{io, thread, time};
+
+const SLEEP_MS: u64 = 10;
+
+fn main() -> io::Result<()> {
+    // a build with phases download (20 %), compile (70 %) and link (10 %)
+    let mut build = NestingBar::new().timed();
+    build.set_len(20);
+    build.inner_mut().set_layout(Layout::Tree);
+    let download = build
+        .inner_mut()
+        .add_child("download", 0.2, MappingBar::with_range(0, 60));
+    let compile = build
+        .inner_mut()
+        .add_child("compile", 0.7, BernoulliBar::with_goal(40));
+    let link = build
+        .inner_mut()
+        .add_child("link", 0.1, MappingBar::with_range(0, 5));
+
+    let mut printer = Printer::stdout();
+    for value in 0..=60 {
+        build.inner_mut().child_mut(download).set(value);
+        printer.print(&mut build)?;
+        thread::sleep(time::Duration::from_millis(SLEEP_MS));
+    }
+    build.inner_mut().child_mut(download).finish();
+
+    for value in 0..60 {
+        // every third unit fails to compile and is retried
+        build.inner_mut().child_mut(compile).add(value % 3 != 0);
+        printer.print(&mut build)?;
+        thread::sleep(time::Duration::from_millis(SLEEP_MS));
+    }
+    build.inner_mut().child_mut(compile).finish();
+
+    for value in 0..=5 {
+        build.inner_mut().child_mut(link).set(value);
+        printer.print(&mut build)?;
+        thread::sleep(time::Duration::from_millis(SLEEP_MS));
+    }
+    // finishing the last child finishes the build
+    build.inner_mut().child_mut(link).finish();
+    printer.print(&mut build)
+}
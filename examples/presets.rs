@@ -0,0 +1,16 @@
+use progressing::{
+    clamping::{Bar as ClampingBar, Preset},
+    Baring,
+};
+
+/// Printing all style-presets at 0.55
+fn main() {
+    println!("Printing all style-presets at 0.55");
+    for &preset in Preset::ALL.iter() {
+        let mut progress_bar = ClampingBar::new();
+        progress_bar.set_len(20);
+        progress_bar.set_preset(preset);
+        progress_bar.set(0.55);
+        println!("{:<8} {}", preset, progress_bar);
+    }
+}
@@ -0,0 +1,60 @@
+use progressing::{
+    bernoulli::Bar as BernoulliBar,
+    mapping::Bar as MappingBar,
+    printing::{Mode, Printer},
+    Baring,
+};
+use std::{io, thread, time};
+
+const SLEEP_MS: u64 = 20;
+
+fn main() -> io::Result<()> {
+    // The mode depends on stderr being a terminal.
+    // Try redirecting stderr to a file or setting PROGRESSING_MODE=lines.
+    let mut printer = Printer::stderr();
+    println!("Printing in mode {:?}", printer.mode());
+    counting(&mut printer)?;
+
+    // the mode can be chosen explicitly as well
+    println!();
+    println!("Printing in mode {:?}", Mode::Lines);
+    bernoulli(&mut Printer::with(io::stdout(), Mode::Lines))
+}
+
+fn counting<W>(printer: &mut Printer<W>) -> io::Result<()>
+where
+    W: io::Write,
+{
+    let mut progress_bar = MappingBar::with_range(0, 100).timed();
+    progress_bar.set_len(20);
+
+    for value in 0..=100 {
+        progress_bar.set(value);
+        printer.print(&mut progress_bar)?;
+
+        if value == 50 {
+            printer.println("Halfway there")?;
+        }
+
+        // sleep for visual effects ;)
+        thread::sleep(time::Duration::from_millis(SLEEP_MS));
+    }
+    progress_bar.finish();
+    printer.print(&mut progress_bar)
+}
+
+fn bernoulli<W>(printer: &mut Printer<W>) -> io::Result<()>
+where
+    W: io::Write,
+{
+    let mut progress_bar = BernoulliBar::with_goal(50).timed();
+    progress_bar.set_len(20);
+
+    for value in 0..100 {
+        // job is successful if value is even
+        progress_bar.add(value % 2 == 0);
+        printer.print(&mut progress_bar)?;
+    }
+    progress_bar.finish();
+    printer.print(&mut progress_bar)
+}
@@ -0,0 +1,30 @@
+use progressing::{
+    mapping::Bar as MappingBar, parallel::ParallelProgressIterator, printing::Printer, Baring,
+};
+use rayon::prelude::*;
+use std::{thread, time};
+
+const SLEEP_MS: u64 = 2;
+
+fn main() {
+    let mut progress_bar = MappingBar::with_range(0usize, 1_000).timed();
+    progress_bar.set_len(20);
+
+    let primes = (0..1_000usize)
+        .into_par_iter()
+        .progress_with(progress_bar, Printer::stdout().shared())
+        .filter(|&n| {
+            // sleep for visual effects ;)
+            thread::sleep(time::Duration::from_millis(SLEEP_MS));
+            is_prime(n)
+        })
+        .count();
+    println!("Found {} primes", primes);
+}
+
+fn is_prime(n: usize) -> bool {
+    n >= 2
+        && (2..)
+            .take_while(|d| d * d <= n)
+            .all(|d| n % d != 0)
+}
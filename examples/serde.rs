@@ -0,0 +1,30 @@
+use progressing::{
+    clamping, mapping,
+    mapping::Bar as MappingBar,
+    snapshot::{Snapshot, Snapshotting},
+    Baring,
+};
+
+fn main() -> serde_json::Result<()> {
+    // missing fields of clamping-configs are filled with defaults
+    let cfg: clamping::Config = serde_json::from_str(r#"{ "bar_len": 20, "style": "(#-.)" }"#)?;
+    let mut progress_bar = clamping::Bar::with(cfg);
+    progress_bar.set(0.3);
+    println!("{}", progress_bar);
+
+    // mapping-configs can be stored and loaded as well
+    let json = serde_json::to_string(&mapping::Config::with(-9, 5))?;
+    println!("{}", json);
+    let cfg: mapping::Config<i32> = serde_json::from_str(&json)?;
+    let mut progress_bar = MappingBar::with(cfg).timed();
+    progress_bar.set(4);
+    println!("{}", progress_bar);
+
+    // snapshots capture the bar's state
+    let json = serde_json::to_string(&progress_bar.snapshot())?;
+    println!("{}", json);
+    let snapshot: Snapshot<i32> = serde_json::from_str(&json)?;
+    println!("{:?}", snapshot);
+
+    Ok(())
+}
@@ -16,10 +16,16 @@ fn main() {
     styles();
     println!();
     remember_progress();
+    println!();
+    lifecycle();
+    println!();
+    prefix_and_message();
+    println!();
+    fitting_terminal();
 }
 
 /// Printing value 0.3 clamped to [0, 1]
-/// [=====>------------]
+/// [=====>............]
 fn clamped() {
     println!("Printing value 0.3 clamped to [0, 1]");
     let mut progress_bar = ClampingBar::new();
@@ -29,7 +35,7 @@ fn clamped() {
 }
 
 /// Mapping from [-9, 5] to [0, 1]
-/// [================>-] (4 / 5)
+/// [================>.] ( 4/5)
 fn mapped() {
     println!("Mapping from [-9, 5] to [0, 1]");
     let mut progress_bar = MappingBar::with_range(-9, 5);
@@ -39,7 +45,7 @@ fn mapped() {
 }
 
 /// Mapping from [-9, 5] to [0, 1], but with time-approximation
-/// [================>-] (4 / 5) ~ 2 min
+/// [================>.] ( 4/5) ~   2s
 fn timed_mapped() {
     println!("Mapping from [-9, 5] to [0, 1], but with time-approximation");
     let mut progress_bar = MappingBar::with_range(-9, 5).timed();
@@ -49,7 +55,7 @@ fn timed_mapped() {
 }
 
 /// Bernoulli-Bar counting successes (42 / 60) and attempts (# 130)
-/// [============>-----] (42 / 60 # 130)
+/// [============>.....] (42/60) #130
 fn bernoulli() {
     println!("Bernoulli-Bar counting successes (42 / 60) and attempts (# 130)");
     let mut progress_bar = BernoulliBar::with_goal(60);
@@ -108,5 +114,52 @@ fn remember_progress() {
 
         std::thread::sleep(std::time::Duration::from_millis(100));
     }
+    progress_bar.finish();
+    println!("{}", progress_bar);
+}
+
+/// Finished, abandoned and failed bars render their final frame
+/// [=====>............] ( 3/10) done in 0s skipped the rest
+/// [=====X............] ( 3/10) failed after 0s
+fn lifecycle() {
+    println!("Finished, abandoned and failed bars");
+    let mut progress_bar = MappingBar::with_range(0, 10).timed();
+    progress_bar.set_len(20);
+    progress_bar.set(3);
+    progress_bar.finish_with_message("skipped the rest");
+    println!("{}", progress_bar);
+
+    // further progress is ignored
+    progress_bar.set(7);
+    println!("{}", progress_bar);
+
+    let mut progress_bar = MappingBar::with_range(0, 10).timed();
+    progress_bar.set_len(20);
+    progress_bar.set(3);
+    progress_bar.fail();
+    println!("{}", progress_bar);
+}
+
+/// Bars can carry a prefix and a message, e.g. a task-name and the current file
+/// compiling [=====>............] ( 3/10) src/a_very_long_directory_name/with_a_v…
+fn prefix_and_message() {
+    println!("Prefix and message");
+    let mut progress_bar = MappingBar::with_range(0, 10);
+    progress_bar.set_len(20);
+    progress_bar.set_prefix("compiling");
+    progress_bar.set(3);
+    progress_bar.set_message("src/a_very_long_directory_name/with_a_very_long_file_name.rs");
+    println!("{}", progress_bar);
+}
+
+/// The bar fills the terminal's width, but prints only its percentage if the terminal is too narrow
+/// downloading [=================>........................................] ( 3/10)
+/// downloading  30% ( 3/10)
+fn fitting_terminal() {
+    println!("Fitting the terminal's width");
+    let mut progress_bar = MappingBar::with_range(0, 10);
+    progress_bar.set_prefix("downloading");
+    progress_bar.fit_terminal(10);
+    progress_bar.set(3);
     println!("{}", progress_bar);
 }
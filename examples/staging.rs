@@ -0,0 +1,31 @@
+use progressing::{
+    mapping::Bar as MappingBar, printing::Printer, staging::Bar as StagingBar, Baring,
+};
+use std::{io, thread, time};
+
+const SLEEP_MS: u64 = 5;
+
+fn main() -> io::Result<()> {
+    let stages = vec!["Fetching", "Compiling", "Testing", "Packaging"];
+    let sizes = [20, 60, 40, 10];
+
+    let mut pipeline = StagingBar::with_stages(stages, MappingBar::with_range(0, sizes[0]));
+    pipeline.set_len(20);
+
+    let mut printer = Printer::stdout();
+    for (stage, &size) in sizes.iter().enumerate() {
+        if stage > 0 {
+            // the remaining time is approximated by the stages done so far
+            pipeline.next_stage(MappingBar::with_range(0, size));
+        }
+        for value in 0..=size {
+            pipeline.set(value);
+            printer.print(&mut pipeline)?;
+
+            // sleep for visual effects ;)
+            thread::sleep(time::Duration::from_millis(SLEEP_MS));
+        }
+    }
+    pipeline.finish();
+    printer.print(&mut pipeline)
+}
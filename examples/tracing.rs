@@ -0,0 +1,32 @@
+use progressing::{printing::Printer, tracing::Layer as ProgressLayer};
+use std::{thread, time};
+use tracing::{info, info_span};
+use tracing_subscriber::{layer::SubscriberExt, Registry};
+
+const SLEEP_MS: u64 = 20;
+
+fn main() {
+    // spans with field `progress.total` get bars
+    let subscriber = Registry::default().with(ProgressLayer::new(Printer::stderr().shared()));
+
+    tracing::subscriber::with_default(subscriber, || {
+        let build = info_span!("build", progress.total = 3);
+        let _build_guard = build.enter();
+
+        for crate_name in &["log", "libc", "progressing"] {
+            // child-spans are shown as indented sub-bars
+            let compile = info_span!("compiling", progress.total = 20);
+            let _compile_guard = compile.enter();
+            for _ in 0..20 {
+                info!(progress.inc = 1, progress.message = crate_name);
+
+                // sleep for visual effects ;)
+                thread::sleep(time::Duration::from_millis(SLEEP_MS));
+            }
+            drop(_compile_guard);
+
+            // events update the nearest span with a bar
+            info!(progress.inc = 1);
+        }
+    });
+}
@@ -1,9 +1,16 @@
-use crate::{mapping, timing, Baring};
-use std::{
+use crate::{
+    clamping::{Effect, FmtFn, Orientation, Preset},
+    mapping, timing, Baring, State, Surrounding,
+};
+use alloc::string::String;
+use core::{
     fmt::{self, Display},
     ops::{Add, AddAssign, Div, Sub},
+    time::Duration,
 };
+use log::warn;
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Config {}
 
 /// A progress-bar counting successes (e.g. `42 out of 60`) and respective attempts (e.g. `130`).
@@ -25,7 +32,7 @@ pub struct Config {}
 /// ```
 #[derive(Debug)]
 pub struct Bar {
-    bar: mapping::Bar<usize>,
+    pub(crate) bar: mapping::Bar<usize>,
     attempts: usize,
 }
 
@@ -37,8 +44,74 @@ impl Bar {
         }
     }
 
+    #[cfg(feature = "std")]
     pub fn timed(self) -> timing::Bar<Bar> {
-        timing::Bar::with(self)
+        self.timed_by(timing::std_clock)
+    }
+
+    /// Measures time by the given clock, returning the time since an arbitrary, but fixed point in time (e.g. a hardware-timer).
+    pub fn timed_by(self, clock: fn() -> Duration) -> timing::Bar<Bar> {
+        timing::Bar::with(self, clock, |bar| bar.bar.bar.progress())
+    }
+
+    pub fn set_style<S>(&mut self, style: S)
+    where
+        S: Into<String>,
+    {
+        self.bar.set_style(style)
+    }
+
+    /// Sets the style to the given preset (see `clamping::Bar::set_preset(...)`).
+    pub fn set_preset(&mut self, preset: Preset) {
+        self.bar.set_preset(preset)
+    }
+
+    pub fn set_finished_style<S>(&mut self, style: S)
+    where
+        S: Into<String>,
+    {
+        self.bar.set_finished_style(style)
+    }
+
+    pub fn set_failed_style<S>(&mut self, style: S)
+    where
+        S: Into<String>,
+    {
+        self.bar.set_failed_style(style)
+    }
+
+    pub fn set_orientation(&mut self, orientation: Orientation) {
+        self.bar.set_orientation(orientation)
+    }
+
+    /// Shows the percentage centered inside the bar (see `clamping::Bar::set_labeled(...)`).
+    pub fn set_labeled(&mut self, is_labeled: bool) {
+        self.bar.set_labeled(is_labeled)
+    }
+
+    /// Sets the colored effect of the running bar (see `clamping::Effect`).
+    pub fn set_effect(&mut self, effect: Effect) {
+        self.bar.set_effect(effect)
+    }
+
+    /// Enables or disables colors (see `clamping::Bar::set_colored(...)`).
+    pub fn set_colored(&mut self, is_colored: bool) {
+        self.bar.set_colored(is_colored)
+    }
+
+    /// Returns everything following the bar itself, e.g. ` (42/60) #130`.
+    /// The attempts are padded to one digit more than the goal, since they usually exceed it.
+    pub(crate) fn suffix(&self) -> impl Display + '_ {
+        FmtFn(move |f| {
+            let width = self.bar.width() + 1;
+            write!(
+                f,
+                "{} #{:>width$}",
+                self.bar.suffix(),
+                self.attempts,
+                width = width
+            )
+        })
     }
 }
 
@@ -53,6 +126,10 @@ impl Baring for Bar {
         self.bar.set_len(new_bar_len)
     }
 
+    fn fit_terminal(&mut self, min_bar_len: usize) {
+        self.bar.fit_terminal(min_bar_len)
+    }
+
     fn progress(&self) -> Progress {
         Progress {
             successes: self.bar.progress(),
@@ -64,6 +141,14 @@ impl Baring for Bar {
     where
         P: Into<Progress>,
     {
+        if self.is_done() {
+            warn!(
+                "The bar is already done ({:?}), hence new progress is ignored.",
+                self.state()
+            );
+            return;
+        }
+
         let outcome = outcome.into();
         self.bar.set(outcome.successes);
         self.attempts = outcome.attempts;
@@ -90,11 +175,53 @@ impl Baring for Bar {
     fn remember_significant_progress(&mut self) {
         self.bar.remember_significant_progress()
     }
+
+    fn state(&self) -> State {
+        self.bar.state()
+    }
+
+    fn set_state(&mut self, new_state: State) {
+        self.bar.set_state(new_state)
+    }
+
+    fn set_prefix<S>(&mut self, prefix: S)
+    where
+        S: Into<String>,
+    {
+        self.bar.set_prefix(prefix)
+    }
+
+    fn set_message<S>(&mut self, msg: S)
+    where
+        S: Into<String>,
+    {
+        self.bar.set_message(msg)
+    }
+
+    fn tick(&mut self) {
+        self.bar.tick()
+    }
+
+    fn set_stalled(&mut self, is_stalled: bool) {
+        self.bar.set_stalled(is_stalled)
+    }
+}
+
+impl Surrounding for Bar {
+    fn fmt_surrounded<L, T>(&self, f: &mut fmt::Formatter<'_>, lead: L, trail: T) -> fmt::Result
+    where
+        L: Display,
+        T: Display,
+    {
+        self.bar
+            .bar
+            .fmt_line_with(f, lead, format_args!("{}{}", self.suffix(), trail))
+    }
 }
 
 impl Display for Bar {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{} #{}", self.bar, self.attempts)
+        self.bar.bar.fmt_line(f, self.suffix())
     }
 }
 
@@ -0,0 +1,336 @@
+//! The binary `progressing` (feature `cli`), showing a bar on `stderr` for piped data or numbers, similar to `pv`.
+//!
+//! ```text
+//! # passing data through, counting bytes (or lines with `--lines`)
+//! cat data.bin | progressing --size 1048576 > copy.bin
+//!
+//! # rendering numeric progress, one value per line (e.g. `42` or `42/100`)
+//! ./script.sh | progressing values --end 100 --eta
+//!
+//! # counting successes and attempts, one pair per line (e.g. `42/130`)
+//! ./script.sh | progressing attempts --goal 60
+//! ```
+
+use clap::{Args, Parser, Subcommand};
+use progressing::{
+    bernoulli::{self, Bar as BernoulliBar},
+    clamping::Preset,
+    mapping::{Bar as MappingBar, ByteUnits, Notation},
+    printing::Printer,
+    Baring,
+};
+use std::{
+    error::Error,
+    fmt::Display,
+    io::{self, BufRead, Read, Write},
+    process::ExitCode,
+};
+
+/// Size of the chunks passed through from stdin to stdout
+const BUFFER_SIZE: usize = 64 * 1024;
+
+/// Shows a progress-bar on stderr for piped data or numbers.
+#[derive(Debug, Parser)]
+#[command(version, args_conflicts_with_subcommands = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[command(flatten)]
+    pipe: PipeArgs,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Passes stdin through to stdout, counting bytes or lines (default)
+    Pipe(PipeArgs),
+    /// Reads progress from stdin, one value per line, e.g. `42` or `42/100`
+    Values(ValuesArgs),
+    /// Reads successes and attempts from stdin, one pair per line, e.g. `42/130`
+    Attempts(AttemptsArgs),
+}
+
+#[derive(Debug, Args)]
+struct PipeArgs {
+    /// Expected number of bytes (or lines), otherwise only the count is shown
+    #[arg(short, long)]
+    size: Option<usize>,
+    /// Counts lines instead of bytes
+    #[arg(short, long)]
+    lines: bool,
+    #[command(flatten)]
+    bar: BarArgs,
+}
+
+#[derive(Debug, Args)]
+struct ValuesArgs {
+    /// Start of the range
+    #[arg(long, default_value_t = 0, allow_negative_numbers = true)]
+    start: i64,
+    /// End of the range, otherwise taken from the first line (e.g. `42/100`)
+    #[arg(short, long, allow_negative_numbers = true)]
+    end: Option<i64>,
+    #[command(flatten)]
+    bar: BarArgs,
+}
+
+#[derive(Debug, Args)]
+struct AttemptsArgs {
+    /// Number of needed successes
+    #[arg(short, long)]
+    goal: usize,
+    #[command(flatten)]
+    bar: BarArgs,
+}
+
+#[derive(Debug, Args)]
+struct BarArgs {
+    /// Style of 5 characters, e.g. `[=>.]`
+    #[arg(long, default_value = "[=>.]")]
+    style: String,
+    /// Named style (classic, hashes, blocks, thin, dots, emoji), replacing `--style`
+    #[arg(long)]
+    preset: Option<Preset>,
+    /// Length of the bar, otherwise filling the terminal
+    #[arg(short, long)]
+    width: Option<usize>,
+    /// Text in front of the bar, e.g. a file-name
+    #[arg(short, long)]
+    prefix: Option<String>,
+    /// Shows elapsed and approximated remaining time
+    #[arg(long)]
+    eta: bool,
+}
+
+impl BarArgs {
+    fn apply<B>(&self, bar: &mut B)
+    where
+        B: Baring,
+    {
+        match self.width {
+            Some(width) => bar.set_len(width),
+            None => bar.fit_terminal(10),
+        }
+        if let Some(prefix) = &self.prefix {
+            bar.set_prefix(prefix.as_str());
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let result = match cli.command {
+        None => pipe(cli.pipe),
+        Some(Command::Pipe(args)) => pipe(args),
+        Some(Command::Values(args)) => values(args),
+        Some(Command::Attempts(args)) => attempts(args),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("progressing: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn pipe(args: PipeArgs) -> Result<(), Box<dyn Error>> {
+    let unit = if args.lines { "lines" } else { "bytes" };
+
+    let size = match args.size {
+        Some(size) => size,
+        None => {
+            let mut printer = Printer::stderr();
+            let prefix = args.bar.prefix.as_deref().unwrap_or("");
+            let result = copy(args.lines, |count| {
+                printer.draw(format_args!("{}{} {}", prefix, count, unit))
+            });
+            printer.clear()?;
+            let count = result?;
+            printer.println(format_args!("{}{} {}", prefix, count, unit))?;
+            return Ok(());
+        }
+    };
+
+    let mut bar = MappingBar::with_range(0, size);
+    bar.set_style(args.bar.style.as_str());
+    if let Some(preset) = args.bar.preset {
+        bar.set_preset(preset);
+    }
+    if !args.lines {
+        bar.set_notation(Notation::Bytes(ByteUnits::Iec));
+    }
+    if args.bar.eta {
+        pipe_with(bar.timed(), &args)
+    } else {
+        pipe_with(bar, &args)
+    }
+}
+
+fn pipe_with<B>(mut bar: B, args: &PipeArgs) -> Result<(), Box<dyn Error>>
+where
+    B: Baring<Progress = usize> + Display,
+{
+    args.bar.apply(&mut bar);
+
+    let mut printer = Printer::stderr();
+    printer.print(&mut bar)?;
+    let result = copy(args.lines, |count| {
+        bar.set(count);
+        printer.print(&mut bar)
+    });
+    finish(&mut bar, &mut printer, result.is_ok())?;
+    result?;
+    Ok(())
+}
+
+/// Passes stdin through to stdout, calling `on_progress` with the number of bytes (or lines) passed so far.
+/// Returns the final number.
+fn copy<F>(is_counting_lines: bool, mut on_progress: F) -> io::Result<usize>
+where
+    F: FnMut(usize) -> io::Result<()>,
+{
+    let mut stdin = io::stdin().lock();
+    let mut stdout = io::stdout().lock();
+    let mut buffer = vec![0; BUFFER_SIZE];
+    let mut count = 0;
+
+    loop {
+        let n = match stdin.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        stdout.write_all(&buffer[..n])?;
+
+        count += if is_counting_lines {
+            buffer[..n].iter().filter(|&&byte| byte == b'\n').count()
+        } else {
+            n
+        };
+        on_progress(count)?;
+    }
+    stdout.flush()?;
+    Ok(count)
+}
+
+fn values(args: ValuesArgs) -> Result<(), Box<dyn Error>> {
+    let mut lines = progress_lines();
+
+    // the end is needed before creating the bar
+    let mut first = None;
+    let end = match args.end {
+        Some(end) => end,
+        None => {
+            let (k, end) = match lines.next().transpose()? {
+                Some(line) => parse_pair::<i64>(&line)?,
+                None => return Ok(()),
+            };
+            first = Some(k);
+            end.ok_or("missing end, neither given by `--end` nor by the first line")?
+        }
+    };
+    let values = first.into_iter().map(Ok).chain(lines.map(|line| {
+        let (k, _end) = parse_pair::<i64>(&line?)?;
+        Ok(k)
+    }));
+
+    let mut bar = MappingBar::with_range(args.start, end);
+    bar.set_style(args.bar.style.as_str());
+    if let Some(preset) = args.bar.preset {
+        bar.set_preset(preset);
+    }
+    if args.bar.eta {
+        show(bar.timed(), &args.bar, values)
+    } else {
+        show(bar, &args.bar, values)
+    }
+}
+
+fn attempts(args: AttemptsArgs) -> Result<(), Box<dyn Error>> {
+    let outcomes = progress_lines().map(|line| {
+        let (successes, attempts) = parse_pair::<usize>(&line?)?;
+        Ok(bernoulli::Progress {
+            successes,
+            attempts: attempts.unwrap_or(successes),
+        })
+    });
+
+    let mut bar = BernoulliBar::with_goal(args.goal);
+    bar.set_style(args.bar.style.as_str());
+    if let Some(preset) = args.bar.preset {
+        bar.set_preset(preset);
+    }
+    if args.bar.eta {
+        show(bar.timed(), &args.bar, outcomes)
+    } else {
+        show(bar, &args.bar, outcomes)
+    }
+}
+
+/// Sets the bar to every given progress, finishing it at the end or failing it at the first error.
+fn show<B, I>(mut bar: B, args: &BarArgs, progresses: I) -> Result<(), Box<dyn Error>>
+where
+    B: Baring + Display,
+    I: IntoIterator<Item = Result<B::Progress, Box<dyn Error>>>,
+{
+    args.apply(&mut bar);
+
+    let mut printer = Printer::stderr();
+    printer.print(&mut bar)?;
+    for progress in progresses {
+        match progress {
+            Ok(progress) => {
+                bar.set(progress);
+                printer.print(&mut bar)?;
+            }
+            Err(e) => {
+                finish(&mut bar, &mut printer, false)?;
+                return Err(e);
+            }
+        }
+    }
+    finish(&mut bar, &mut printer, true)?;
+    Ok(())
+}
+
+fn finish<B, W>(bar: &mut B, printer: &mut Printer<W>, is_successful: bool) -> io::Result<()>
+where
+    B: Baring + Display,
+    W: Write,
+{
+    if is_successful {
+        bar.finish();
+    } else {
+        bar.fail();
+    }
+    printer.print(bar)
+}
+
+/// Returns the non-empty lines of stdin.
+fn progress_lines() -> impl Iterator<Item = io::Result<String>> {
+    io::stdin().lock().lines().filter(|line| match line {
+        Ok(line) => !line.trim().is_empty(),
+        Err(_) => true,
+    })
+}
+
+/// Parses lines like `42` or `42/100`.
+fn parse_pair<N>(line: &str) -> Result<(N, Option<N>), Box<dyn Error>>
+where
+    N: std::str::FromStr,
+    N::Err: Error + 'static,
+{
+    let line = line.trim();
+    let parse = |text: &str| {
+        text.trim()
+            .parse::<N>()
+            .map_err(|e| format!("invalid line {:?}: {}", line, e))
+    };
+
+    match line.split_once('/') {
+        Some((first, second)) => Ok((parse(first)?, Some(parse(second)?))),
+        None => Ok((parse(line)?, None)),
+    }
+}
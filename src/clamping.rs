@@ -1,14 +1,19 @@
-use crate::{timing, Baring};
-use log::warn;
-use std::{
+use crate::{terminal, timing, Baring, State, Surrounding};
+use alloc::string::String;
+use core::{
     cmp::min,
     fmt::{self, Display},
+    str::FromStr,
+    time::Duration,
 };
+use log::warn;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 #[derive(Debug)]
 struct PrintController {
     last_printed_progress: Option<f64>,
     interesting_progress_step: f64,
+    has_changed_look: bool,
 }
 
 impl PrintController {
@@ -16,6 +21,7 @@ impl PrintController {
         PrintController {
             last_printed_progress: None,
             interesting_progress_step,
+            has_changed_look: false,
         }
     }
 
@@ -25,18 +31,44 @@ impl PrintController {
     }
 
     fn has_progressed_significantly(&self, progress: f64) -> bool {
-        self.map(progress) > self.map(self.last_printed_progress.unwrap_or(0.0))
+        self.has_changed_look
+            || self.map(progress) > self.map(self.last_printed_progress.unwrap_or(0.0))
+    }
+
+    /// Prefix, message or stall have changed, which is worth printing.
+    fn mark_changed_look(&mut self) {
+        self.has_changed_look = true;
     }
 
     fn update(&mut self, progress: f64) {
         self.last_printed_progress = Some(progress);
+        self.has_changed_look = false;
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Config {
     pub bar_len: usize,
+    /// If true, the bar fills the terminal's columns left by prefix, suffix and message, ignoring `bar_len`.
+    pub is_fitting_terminal: bool,
+    /// If the terminal is too narrow for a bar of this length, only the percentage is printed.
+    pub min_bar_len: usize,
     pub style: String,
+    /// Style of the final frame after `finish()` (completely filled) or `abandon()`
+    pub finished_style: String,
+    /// Style of the final frame after `fail()`
+    pub failed_style: String,
     pub interesting_progress_step: f64,
+    /// Longer prefixes are truncated with an ellipsis
+    pub max_prefix_len: usize,
+    /// Longer messages are truncated with an ellipsis
+    pub max_message_len: usize,
+    pub orientation: Orientation,
+    /// If true, the percentage is shown centered inside the bar.
+    pub is_labeled: bool,
+    /// Colored effect while running, if the terminal supports colors
+    pub effect: Effect,
 }
 
 impl Config {
@@ -49,12 +81,309 @@ impl Default for Config {
     fn default() -> Config {
         Config {
             bar_len: 42,
+            is_fitting_terminal: false,
+            min_bar_len: 10,
             style: String::from("[=>.]"),
+            finished_style: String::from("[=>.]"),
+            failed_style: String::from("[=X.]"),
             interesting_progress_step: 0.1,
+            max_prefix_len: 20,
+            max_message_len: 40,
+            orientation: Orientation::default(),
+            is_labeled: false,
+            effect: Effect::default(),
+        }
+    }
+}
+
+/// The direction a bar fills in.
+///
+///
+/// # Mini-Example
+///
+/// ```
+/// use progressing::{
+///     clamping::{Bar as ClampingBar, Orientation},
+///     Baring,
+/// };
+///
+/// fn main() {
+///     let mut progress_bar = ClampingBar::new();
+///     progress_bar.set_len(20);
+///     progress_bar.set(0.3);
+///
+///     progress_bar.set_orientation(Orientation::RightToLeft);
+///     assert_eq!(progress_bar.to_string(), "[............<=====]");
+///
+///     progress_bar.set_orientation(Orientation::Reversed);
+///     assert_eq!(progress_bar.to_string(), "[============>.....]");
+///
+///     progress_bar.set_orientation(Orientation::LeftToRight);
+///     progress_bar.set_labeled(true);
+///     assert_eq!(progress_bar.to_string(), "[=====>.30%........]");
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Orientation {
+    /// Fills from left to right, e.g. `[===>....]`
+    #[default]
+    LeftToRight,
+    /// Fills from right to left with a mirrored hat, e.g. `[....<===]`
+    RightToLeft,
+    /// Empties from right to left, showing the remaining part, e.g. for countdowns
+    Reversed,
+}
+
+/// Colored effects of a running bar, shown only if colors are enabled (see `Bar::set_colored(...)`).
+/// Without colors, and in the final frame, the bar falls back to its plain style.
+///
+/// By default, a `printing::Printer` decides about colors depending on its output (see `terminal::supports_color_on(...)`),
+/// while bars displayed otherwise (e.g. by `println!`) are plain, unless colors are forced (see `terminal::is_color_forced()`).
+///
+/// Animated effects move to their next frame with every `Baring::tick()`, which a `ticking::Ticker` calls before every print.
+/// A `Printer` in mode `Lines` removes the colors, hence logs stay plain text.
+///
+///
+/// # Mini-Example
+///
+/// ```
+/// use progressing::{
+///     clamping::{Bar as ClampingBar, Effect},
+///     Baring,
+/// };
+///
+/// fn main() {
+///     let mut progress_bar = ClampingBar::new();
+///     progress_bar.set_len(20);
+///     progress_bar.set(0.3);
+///     progress_bar.set_effect(Effect::Gradient);
+///
+///     progress_bar.set_colored(false);
+///     assert_eq!(progress_bar.to_string(), "[=====>............]");
+///
+///     // red at the left end, turning green towards the right end
+///     progress_bar.set_colored(true);
+///     assert!(progress_bar.to_string().starts_with("[\x1b[38;2;255;0;0m="));
+///     assert!(progress_bar.to_string().ends_with("\x1b[0m............]"));
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Effect {
+    /// The plain style without colors
+    #[default]
+    None,
+    /// Colors the line from red at the bar's left end to green at its right end.
+    Gradient,
+    /// A bright highlight running along the line towards the hat, one cell per tick.
+    Shimmer,
+    /// The whole line fading in and out, shown automatically while a timed bar has stalled (see `timing::Bar::set_stall_timeout(...)`).
+    Pulse,
+}
+
+impl Effect {
+    /// Number of cells of the highlight of `Effect::Shimmer`
+    const SHIMMER_LEN: usize = 3;
+    /// Number of ticks of a whole fade of `Effect::Pulse`
+    const PULSE_PERIOD: usize = 16;
+
+    /// Returns the color (red, green, blue) of the filled cell at the given index, if any.
+    fn color(self, i: usize, reached: usize, inner_bar_len: usize, tick: usize) -> Option<Rgb> {
+        match self {
+            Effect::None => None,
+            Effect::Gradient => {
+                let t = i as f64 / inner_bar_len.saturating_sub(1).max(1) as f64;
+                let red = (510.0 * (1.0 - t)).min(255.0) as u8;
+                let green = (510.0 * t).min(255.0) as u8;
+                Some((red, green, 0))
+            }
+            Effect::Shimmer => {
+                // the highlight enters at the left end and leaves behind the hat
+                let head = tick % (reached + 1 + Effect::SHIMMER_LEN);
+                if i <= head && head - i < Effect::SHIMMER_LEN {
+                    Some((255, 255, 255))
+                } else {
+                    Some((128, 128, 128))
+                }
+            }
+            Effect::Pulse => {
+                let phase = tick % Effect::PULSE_PERIOD;
+                let step = min(phase, Effect::PULSE_PERIOD - phase);
+                let level = (80 + step * 175 / (Effect::PULSE_PERIOD / 2)) as u8;
+                Some((level, level, level))
+            }
+        }
+    }
+}
+
+/// Returns whether a `printing::Printer` is printing to an output supporting colors on the current thread.
+#[cfg(feature = "std")]
+fn is_printing_colored() -> bool {
+    crate::printing::is_coloring()
+}
+
+#[cfg(not(feature = "std"))]
+fn is_printing_colored() -> bool {
+    false
+}
+
+/// A color of red, green and blue
+type Rgb = (u8, u8, u8);
+
+/// Switches to the given color (as ANSI escape code), if it differs from the current one.
+/// `None` resets to the terminal's color.
+fn fmt_color(
+    f: &mut fmt::Formatter<'_>,
+    current: &mut Option<Rgb>,
+    color: Option<Rgb>,
+) -> fmt::Result {
+    if *current == color {
+        return Ok(());
+    }
+    *current = color;
+    match color {
+        Some((red, green, blue)) => write!(f, "\x1b[38;2;{};{};{}m", red, green, blue),
+        None => f.write_str("\x1b[0m"),
+    }
+}
+
+/// Named styles, set by `Bar::set_preset(...)` or by their prefixed name wherever a style is expected, e.g. `"preset:blocks"` in a config.
+/// Unprefixed styles are always taken literally, e.g. `"ascii"`.
+///
+/// Styles of non-ASCII chars fall back to `Preset::Classic` if the terminal doesn't support UTF-8 (see `terminal::supports_utf8()`).
+/// Wide chars (e.g. of `Preset::Emoji`) take two columns per cell, hence a bar of a given length has fewer cells.
+///
+///
+/// # Mini-Example
+///
+/// ```
+/// use progressing::{
+///     clamping::{Bar as ClampingBar, Preset},
+///     Baring,
+/// };
+///
+/// fn main() {
+///     let mut progress_bar = ClampingBar::new();
+///     progress_bar.set_len(12);
+///     progress_bar.set(0.5);
+///
+///     progress_bar.set_preset(Preset::Hashes);
+///     assert_eq!(progress_bar.to_string(), "[######----]");
+///
+///     // by prefixed name, e.g. from a config
+///     progress_bar.set_style("preset:dots");
+///     println!("{}", progress_bar);
+///
+///     // without prefix, a style is taken literally
+///     progress_bar.set_style("ascii");
+///     assert_eq!(progress_bar.to_string(), "asssssciiiii");
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Preset {
+    /// `[=====>....]`
+    Classic,
+    /// `[######----]`
+    Hashes,
+    /// `│█████▌░░░░│`
+    Blocks,
+    /// `▕━━━━━╸────▏`
+    Thin,
+    /// `⢸⣿⣿⣿⣿⣿⣿⣀⣀⣀⡇`
+    Dots,
+    /// `▕🟩🟩🟩🟨⬜⬜▏`, whose chars are two columns wide in most terminals
+    Emoji,
+}
+
+impl Preset {
+    /// Prefix of a preset's name in a style, e.g. `"preset:blocks"`
+    pub const STYLE_PREFIX: &'static str = "preset:";
+
+    /// All presets, e.g. for listing them
+    pub const ALL: [Preset; 6] = [
+        Preset::Classic,
+        Preset::Hashes,
+        Preset::Blocks,
+        Preset::Thin,
+        Preset::Dots,
+        Preset::Emoji,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Preset::Classic => "classic",
+            Preset::Hashes => "hashes",
+            Preset::Blocks => "blocks",
+            Preset::Thin => "thin",
+            Preset::Dots => "dots",
+            Preset::Emoji => "emoji",
+        }
+    }
+
+    /// Returns the style's 5 chars, regardless of the terminal.
+    pub fn chars(self) -> &'static str {
+        match self {
+            Preset::Classic => "[=>.]",
+            Preset::Hashes => "[##-]",
+            Preset::Blocks => "│█▌░│",
+            Preset::Thin => "▕━╸─▏",
+            Preset::Dots => "⢸⣿⣿⣀⡇",
+            Preset::Emoji => "▕🟩🟨⬜▏",
+        }
+    }
+
+    /// Returns the style's 5 chars, or the ones of `Preset::Classic` if the terminal doesn't support UTF-8.
+    pub fn style(self) -> &'static str {
+        let chars = self.chars();
+        if chars.is_ascii() || terminal::supports_utf8() {
+            chars
+        } else {
+            Preset::Classic.chars()
         }
     }
 }
 
+impl fmt::Display for Preset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+impl FromStr for Preset {
+    type Err = UnknownPreset;
+
+    /// Parses the preset's name, ignoring case.
+    fn from_str(name: &str) -> Result<Preset, UnknownPreset> {
+        Preset::ALL
+            .iter()
+            .copied()
+            .find(|preset| preset.name().eq_ignore_ascii_case(name.trim()))
+            .ok_or(UnknownPreset)
+    }
+}
+
+impl From<Preset> for String {
+    fn from(preset: Preset) -> String {
+        String::from(preset.style())
+    }
+}
+
+/// The error when parsing a `Preset` from an unknown name
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct UnknownPreset;
+
+impl fmt::Display for UnknownPreset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("unknown style-preset")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for UnknownPreset {}
+
 /// A progress-bar clamping values to `[0, 1]`.
 ///
 ///
@@ -74,13 +403,35 @@ impl Default for Config {
 /// }
 /// ```
 ///
-/// It is only optimized for single-length-strings, but strings are more handy than chars and hence used as implementation.
+/// Styles consist of 5 chars, e.g. `[=>.]` or `│█▌░│`, or name a `Preset`, e.g. `preset:blocks`.
 #[derive(Debug)]
 pub struct Bar {
     bar_len: usize,
+    is_fitting_terminal: bool,
+    min_bar_len: usize,
+    /// The terminal's resize-generation when the bar has been printed the last time
+    seen_resize_generation: usize,
     style: String,
+    finished_style: String,
+    failed_style: String,
     progress: f64,
     print_controller: PrintController,
+    state: State,
+    prefix: Option<String>,
+    message: Option<String>,
+    max_prefix_len: usize,
+    max_message_len: usize,
+    orientation: Orientation,
+    is_labeled: bool,
+    effect: Effect,
+    /// Set by `set_colored(...)`, otherwise decided when printing
+    is_colored: Option<bool>,
+    /// Frame of animated effects, increased by `tick()`
+    tick: usize,
+    /// Frame of animated effects, which has been printed latest
+    seen_tick: usize,
+    /// Shows `Effect::Pulse` instead of the effect, see `Baring::set_stalled(...)`
+    is_stalled: bool,
 }
 
 impl Bar {
@@ -91,54 +442,366 @@ impl Bar {
     pub fn with(cfg: Config) -> Bar {
         Bar {
             bar_len: cfg.bar_len,
-            style: cfg.style,
+            is_fitting_terminal: cfg.is_fitting_terminal,
+            min_bar_len: cfg.min_bar_len,
+            seen_resize_generation: if cfg.is_fitting_terminal {
+                terminal::resize_generation()
+            } else {
+                0
+            },
+            style: checked_style(cfg.style),
+            finished_style: checked_style(cfg.finished_style),
+            failed_style: checked_style(cfg.failed_style),
             progress: 0.0,
             print_controller: PrintController::from(cfg.interesting_progress_step),
+            state: State::Running,
+            prefix: None,
+            message: None,
+            max_prefix_len: cfg.max_prefix_len,
+            max_message_len: cfg.max_message_len,
+            orientation: cfg.orientation,
+            is_labeled: cfg.is_labeled,
+            effect: cfg.effect,
+            is_colored: if terminal::is_color_forced() {
+                Some(true)
+            } else {
+                None
+            },
+            tick: 0,
+            seen_tick: 0,
+            is_stalled: false,
         }
     }
 
+    #[cfg(feature = "std")]
     pub fn timed(self) -> timing::Bar<Bar> {
-        timing::Bar::with(self)
+        self.timed_by(timing::std_clock)
     }
 
+    /// Measures time by the given clock, returning the time since an arbitrary, but fixed point in time (e.g. a hardware-timer).
+    pub fn timed_by(self, clock: fn() -> Duration) -> timing::Bar<Bar> {
+        timing::Bar::with(self, clock, |bar| bar.progress)
+    }
+
+    /// Sets the style of 5 chars, e.g. `[=>.]`, or a preset's name prefixed by `Preset::STYLE_PREFIX` (e.g. `preset:blocks`).
     pub fn set_style<S>(&mut self, style: S)
     where
         S: Into<String>,
     {
-        let style = style.into();
+        self.style = checked_style(style);
+    }
+
+    /// Sets the style of the running and of the finished bar to the given preset (see `Preset::style()`).
+    pub fn set_preset(&mut self, preset: Preset) {
+        self.style = String::from(preset.style());
+        self.finished_style = String::from(preset.style());
+    }
+
+    pub fn set_finished_style<S>(&mut self, style: S)
+    where
+        S: Into<String>,
+    {
+        self.finished_style = checked_style(style);
+    }
+
+    pub fn set_failed_style<S>(&mut self, style: S)
+    where
+        S: Into<String>,
+    {
+        self.failed_style = checked_style(style);
+    }
+
+    pub fn set_orientation(&mut self, orientation: Orientation) {
+        self.orientation = orientation;
+    }
+
+    /// Shows the percentage centered inside the bar, overlaying the line.
+    /// The label is left out if the bar is too short.
+    pub fn set_labeled(&mut self, is_labeled: bool) {
+        self.is_labeled = is_labeled;
+    }
+
+    /// Sets the colored effect of the running bar, which is only shown if colors are enabled.
+    pub fn set_effect(&mut self, effect: Effect) {
+        self.effect = effect;
+    }
+
+    /// Returns whether ticks change the frame printed by a coloring `printing::Printer`.
+    fn is_animated(&self) -> bool {
+        let is_moving = self.is_stalled || matches!(self.effect, Effect::Shimmer | Effect::Pulse);
+        is_moving
+            && self.state == State::Running
+            && self.is_colored != Some(false)
+            && is_printing_colored()
+    }
 
-        if style.len() != 5 {
-            warn!("The bar-style has to consist of 5 characters, e.g. [=>-]");
+    /// Enables or disables colors, regardless of the output.
+    /// By default, a `printing::Printer` enables them if its output supports them, and they are disabled otherwise (see `Effect`).
+    /// Without colors, effects fall back to the plain style.
+    pub fn set_colored(&mut self, is_colored: bool) {
+        self.is_colored = Some(is_colored);
+    }
+
+    /// Writes the whole line, meaning prefix, bar, the given suffix (e.g. ` (4/5)`) and message, without allocating.
+    ///
+    /// If the bar is fitting the terminal, its length fills the remaining columns.
+    /// If the terminal is too narrow, the bar is replaced by its percentage.
+    pub(crate) fn fmt_line<S>(&self, f: &mut fmt::Formatter<'_>, suffix: S) -> fmt::Result
+    where
+        S: Display,
+    {
+        self.fmt_line_with(f, "", suffix)
+    }
+
+    /// Writes the whole line like `fmt_line(...)`, preceded by the given lead (e.g. the stage of a `staging::Bar`).
+    /// Unlike the prefix, the lead isn't truncated.
+    pub(crate) fn fmt_line_with<L, S>(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        lead: L,
+        suffix: S,
+    ) -> fmt::Result
+    where
+        L: Display,
+        S: Display,
+    {
+        let prefix_len = display_width(&lead) + self.prefix_len();
+        let mut message_len = self.message_len(self.max_message_len + 1);
+
+        let mut bar_len = self.bar_len;
+        if self.is_fitting_terminal {
+            if let Some(width) = terminal::width() {
+                let fixed_len = prefix_len + display_width(&suffix);
+
+                if width < fixed_len + self.min_bar_len {
+                    // compact form without bar
+                    let percentage = self.shown_progress() * 100.0;
+                    let percentage_len = display_width(format_args!("{:>3.0}%", percentage));
+                    let message_len = width.saturating_sub(fixed_len + percentage_len);
+                    let message_len = self.message_len(min(message_len, self.max_message_len + 1));
+                    write!(f, "{}", lead)?;
+                    self.fmt_prefix(f)?;
+                    write!(f, "{:>3.0}%{}", percentage, suffix)?;
+                    return self.fmt_message(f, message_len);
+                }
+
+                let max_message_len = width - fixed_len - self.min_bar_len;
+                message_len = self.message_len(min(max_message_len, self.max_message_len + 1));
+                bar_len = width - fixed_len - message_len;
+            }
+        }
+
+        write!(f, "{}", lead)?;
+        self.fmt_prefix(f)?;
+        self.fmt_bar(f, bar_len)?;
+        write!(f, "{}", suffix)?;
+        self.fmt_message(f, message_len)
+    }
+
+    /// Writes the bar itself, e.g. `[=====>------------]`, in its orientation and with its label, if any.
+    fn fmt_bar(&self, f: &mut fmt::Formatter<'_>, bar_len: usize) -> fmt::Result {
+        // calc progress
+        // -> bar needs to be calculated
+        // -> no brackets involved
+        // wide chars (e.g. emojis) take several columns per cell
+        let cell_width = [self.line(), self.hat(), self.empty_line()]
+            .iter()
+            .map(|cell| cell.width())
+            .max()
+            .unwrap_or(1)
+            .max(1);
+        let inner_bar_len = bar_len.saturating_sub(self.brackets_len()) / cell_width;
+        let fraction = match self.orientation {
+            Orientation::LeftToRight | Orientation::RightToLeft => self.shown_progress(),
+            Orientation::Reversed => 1.0 - self.shown_progress(),
+        };
+        let reached: usize = (fraction * inner_bar_len as f64) as usize;
+
+        // the label is centered, if it fits
+        let percentage = self.shown_progress() * 100.0;
+        let label_width = if self.is_labeled {
+            display_width(format_args!("{:.0}%", percentage))
+        } else {
+            0
         };
-        self.style = style;
+        // in cells, which may be wider than the label's chars
+        let label_len = label_width.div_ceil(cell_width);
+        let label_start = if self.is_labeled && label_len < inner_bar_len {
+            Some((inner_bar_len - label_len) / 2)
+        } else {
+            None
+        };
+
+        // effects are shown while running only, the final frame is plain
+        let is_colored = self.is_colored.unwrap_or_else(is_printing_colored);
+        let effect = if is_colored && self.state == State::Running {
+            if self.is_stalled {
+                Effect::Pulse
+            } else {
+                self.effect
+            }
+        } else {
+            Effect::None
+        };
+        // looked up once, since the style's chars may consist of several bytes
+        let (line, hat, empty_line) = (self.line(), self.hat(), self.empty_line());
+
+        f.write_str(self.left_bracket())?;
+        if effect == Effect::None && label_start.is_none() {
+            // plain bars are written run by run instead of cell by cell
+            let hat_len = if reached < inner_bar_len { 1 } else { 0 };
+            let runs = [
+                (line, reached),
+                (hat, hat_len),
+                (empty_line, inner_bar_len - reached - hat_len),
+            ];
+            match self.orientation {
+                Orientation::LeftToRight | Orientation::Reversed => {
+                    for &(cell, count) in runs.iter() {
+                        fmt_repeated(f, cell, count)?;
+                    }
+                }
+                Orientation::RightToLeft => {
+                    for &(cell, count) in runs.iter().rev() {
+                        fmt_repeated(f, mirrored(cell), count)?;
+                    }
+                }
+            }
+            return f.write_str(self.right_bracket());
+        }
+
+        let mut current_color = None;
+        let mut i = 0;
+        while i < inner_bar_len {
+            if Some(i) == label_start {
+                fmt_color(f, &mut current_color, None)?;
+                write!(f, "{:.0}%", percentage)?;
+                // filling the label's last cell
+                for _ in label_width..label_len * cell_width {
+                    f.write_str(" ")?;
+                }
+                i += label_len;
+                continue;
+            }
+
+            // index in a bar filled from left to right
+            let j = match self.orientation {
+                Orientation::LeftToRight | Orientation::Reversed => i,
+                Orientation::RightToLeft => inner_bar_len - 1 - i,
+            };
+            let cell = if j < reached {
+                line
+            } else if j == reached && reached < inner_bar_len {
+                // the hat is cropped if the end of the bar is reached
+                hat
+            } else {
+                empty_line
+            };
+            let cell = match self.orientation {
+                Orientation::LeftToRight | Orientation::Reversed => cell,
+                Orientation::RightToLeft => mirrored(cell),
+            };
+            let is_filled = j < reached || (j == reached && reached < inner_bar_len);
+            let color = if is_filled {
+                effect.color(j, reached, inner_bar_len, self.tick)
+            } else {
+                None
+            };
+            fmt_color(f, &mut current_color, color)?;
+            f.write_str(cell)?;
+            i += 1;
+        }
+        fmt_color(f, &mut current_color, None)?;
+        f.write_str(self.right_bracket())
     }
 
-    fn inner_bar_len(&self) -> usize {
-        self.len() - self.brackets_len()
+    /// Finished bars are shown completely filled, regardless of their progress.
+    fn shown_progress(&self) -> f64 {
+        match self.state {
+            State::Finished => 1.0,
+            _ => self.progress,
+        }
+    }
+
+    /// Returns the length of the prefix in columns, if any, including a trailing space.
+    fn prefix_len(&self) -> usize {
+        match &self.prefix {
+            Some(prefix) => truncated_len(prefix, self.max_prefix_len) + 1,
+            None => 0,
+        }
+    }
+
+    /// Writes the prefix, if any, including a trailing space.
+    fn fmt_prefix(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.prefix {
+            Some(prefix) => {
+                fmt_truncated(f, prefix, self.max_prefix_len)?;
+                f.write_str(" ")
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the length of the message in columns, if any, including a leading space.
+    /// The length is at most `max_len`, or `0` if there is no space for the message.
+    fn message_len(&self, max_len: usize) -> usize {
+        match &self.message {
+            Some(msg) if max_len > 1 => truncated_len(msg, max_len - 1) + 1,
+            _ => 0,
+        }
+    }
+
+    /// Writes the message with the given length (see `message_len(...)`), including a leading space.
+    fn fmt_message(&self, f: &mut fmt::Formatter<'_>, len: usize) -> fmt::Result {
+        match &self.message {
+            Some(msg) if len > 1 => {
+                f.write_str(" ")?;
+                fmt_truncated(f, msg, len - 1)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// The style depends on the bar's state, e.g. a failed bar may look different.
+    fn current_style(&self) -> &str {
+        match self.state {
+            State::Running => &self.style,
+            State::Finished | State::Abandoned => &self.finished_style,
+            State::Failed => &self.failed_style,
+        }
     }
 
     fn brackets_len(&self) -> usize {
-        self.left_bracket().len() + self.right_bracket().len()
+        self.left_bracket().width() + self.right_bracket().width()
+    }
+
+    /// Returns the style's char at the given index, which may consist of several bytes (e.g. `█`).
+    fn style_char(&self, i: usize) -> &str {
+        let style = self.current_style();
+        match style.char_indices().nth(i) {
+            Some((start, c)) => &style[start..start + c.len_utf8()],
+            None => "",
+        }
     }
 
     fn left_bracket(&self) -> &str {
-        &self.style[0..1]
+        self.style_char(0)
     }
 
     fn line(&self) -> &str {
-        &self.style[1..2]
+        self.style_char(1)
     }
 
     fn hat(&self) -> &str {
-        &self.style[2..3]
+        self.style_char(2)
     }
 
     fn empty_line(&self) -> &str {
-        &self.style[3..4]
+        self.style_char(3)
     }
 
     fn right_bracket(&self) -> &str {
-        &self.style[4..5]
+        self.style_char(4)
     }
 }
 
@@ -155,9 +818,17 @@ impl Baring for Bar {
         self.bar_len
     }
 
-    /// panics if length is `< 3`
     fn set_len(&mut self, new_bar_len: usize) {
         self.bar_len = new_bar_len;
+        self.is_fitting_terminal = false;
+    }
+
+    fn fit_terminal(&mut self, min_bar_len: usize) {
+        self.min_bar_len = min_bar_len;
+        if !self.is_fitting_terminal {
+            self.is_fitting_terminal = true;
+            self.seen_resize_generation = terminal::resize_generation();
+        }
     }
 
     fn progress(&self) -> f64 {
@@ -168,15 +839,15 @@ impl Baring for Bar {
     where
         P: Into<f64>,
     {
-        let mut new_progress = new_progress.into();
-
-        if new_progress < 0.0 {
-            new_progress = 0.0;
-        }
-        if 1.0 < new_progress {
-            new_progress = 1.0;
+        if self.is_done() {
+            warn!(
+                "The bar is already done ({:?}), hence new progress is ignored.",
+                self.state
+            );
+            return;
         }
-        self.progress = new_progress;
+
+        self.progress = new_progress.into().clamp(0.0, 1.0);
     }
 
     fn start(&self) -> f64 {
@@ -190,36 +861,211 @@ impl Baring for Bar {
     fn has_progressed_significantly(&self) -> bool {
         self.print_controller
             .has_progressed_significantly(self.progress())
+            || (self.is_fitting_terminal
+                && self.seen_resize_generation != terminal::resize_generation())
+            || (self.seen_tick != self.tick && self.is_animated())
     }
 
     fn remember_significant_progress(&mut self) {
         self.print_controller.update(self.progress());
+        if self.is_fitting_terminal {
+            self.seen_resize_generation = terminal::resize_generation();
+        }
+        self.seen_tick = self.tick;
+    }
+
+    fn state(&self) -> State {
+        self.state
+    }
+
+    fn set_state(&mut self, new_state: State) {
+        self.state = new_state;
+    }
+
+    fn set_prefix<S>(&mut self, prefix: S)
+    where
+        S: Into<String>,
+    {
+        let prefix = Some(prefix.into()).filter(|prefix| !prefix.is_empty());
+        if prefix != self.prefix {
+            self.prefix = prefix;
+            self.print_controller.mark_changed_look();
+        }
+    }
+
+    fn set_message<S>(&mut self, msg: S)
+    where
+        S: Into<String>,
+    {
+        let msg = Some(msg.into()).filter(|msg| !msg.is_empty());
+        if msg != self.message {
+            self.message = msg;
+            self.print_controller.mark_changed_look();
+        }
+    }
+
+    fn tick(&mut self) {
+        self.tick = self.tick.wrapping_add(1);
+    }
+
+    fn set_stalled(&mut self, is_stalled: bool) {
+        if self.is_stalled != is_stalled {
+            self.print_controller.mark_changed_look();
+        }
+        self.is_stalled = is_stalled;
+    }
+}
+
+impl Surrounding for Bar {
+    fn fmt_surrounded<L, T>(&self, f: &mut fmt::Formatter<'_>, lead: L, trail: T) -> fmt::Result
+    where
+        L: Display,
+        T: Display,
+    {
+        self.fmt_line_with(f, lead, trail)
     }
 }
 
 impl Display for Bar {
     /// Progress is clamped to `[0, 1]`.
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        // calc progress
-        // -> bar needs to be calculated
-        // -> no brackets involved
-        let reached: usize = (self.progress * self.inner_bar_len() as f64) as usize;
-
-        let line = self.line().repeat(reached);
-        // crop hat if end of bar is reached
-        let hat = &self.hat()[0..min(self.hat().len(), self.inner_bar_len() - reached)];
-        // fill up rest with empty line
-        let empty_line = self
-            .empty_line()
-            .repeat(self.inner_bar_len() - reached - hat.len());
-        write!(
-            f,
-            "{}{}{}{}{}",
-            self.left_bracket(),
-            line,
-            hat,
-            empty_line,
-            self.right_bracket()
-        )
+        self.fmt_line(f, "")
+    }
+}
+
+/// Returns the number of columns written by `fmt_truncated(...)`.
+fn truncated_len(text: &str, max_len: usize) -> usize {
+    if text.is_ascii() {
+        min(text.len(), max_len)
+    } else {
+        display_width(FmtFn(|f| fmt_truncated(f, text, max_len)))
+    }
+}
+
+/// Writes the given text truncated to `max_len` columns, replacing the last column by an ellipsis if needed.
+pub(crate) fn fmt_truncated(f: &mut fmt::Formatter<'_>, text: &str, max_len: usize) -> fmt::Result {
+    // a text has at most one column per byte
+    if text.len() <= max_len || text.width() <= max_len {
+        return f.write_str(text);
+    }
+    if max_len == 0 {
+        return Ok(());
+    }
+
+    let mut len = 0;
+    let mut end = 0;
+    for (idx, c) in text.char_indices() {
+        len += c.width().unwrap_or(0);
+        if len > max_len - 1 {
+            break;
+        }
+        end = idx + c.len_utf8();
+    }
+    f.write_str(&text[..end])?;
+    f.write_str("…")
+}
+
+/// Writes the given char `count` times, in chunks instead of one call per char.
+fn fmt_repeated(f: &mut fmt::Formatter<'_>, c: &str, count: usize) -> fmt::Result {
+    let mut buffer = [0; 64];
+    if c.is_empty() || c.len() > buffer.len() {
+        for _ in 0..count {
+            f.write_str(c)?;
+        }
+        return Ok(());
+    }
+
+    let chunk_count = buffer.len() / c.len();
+    if let [byte] = c.as_bytes() {
+        buffer.fill(*byte);
+    } else {
+        for i in 0..min(count, chunk_count) {
+            buffer[i * c.len()..(i + 1) * c.len()].copy_from_slice(c.as_bytes());
+        }
+    }
+    // the buffer consists of whole chars only
+    let filled_len = min(count, chunk_count) * c.len();
+    let chunk = core::str::from_utf8(&buffer[..filled_len]).map_err(|_| fmt::Error)?;
+
+    let mut remaining = count;
+    while remaining > 0 {
+        let n = min(remaining, chunk_count);
+        f.write_str(&chunk[..n * c.len()])?;
+        remaining -= n;
+    }
+    Ok(())
+}
+
+/// Displays the output of a closure, e.g. for passing formatted text without allocating.
+pub(crate) struct FmtFn<F>(pub(crate) F)
+where
+    F: Fn(&mut fmt::Formatter<'_>) -> fmt::Result;
+
+impl<F> Display for FmtFn<F>
+where
+    F: Fn(&mut fmt::Formatter<'_>) -> fmt::Result,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (self.0)(f)
+    }
+}
+
+/// Returns the number of columns of the displayed value (e.g. `2` per emoji), without allocating.
+pub(crate) fn display_width<D>(value: D) -> usize
+where
+    D: Display,
+{
+    struct Counter(usize);
+
+    impl fmt::Write for Counter {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            // ASCII is the common case and has one column per byte
+            self.0 += if s.is_ascii() { s.len() } else { s.width() };
+            Ok(())
+        }
     }
+
+    let mut counter = Counter(0);
+    // Counting never fails.
+    let _ = fmt::write(&mut counter, format_args!("{}", value));
+    counter.0
+}
+
+/// Mirrors chars pointing in a direction, e.g. the hat `>` becomes `<`.
+fn mirrored(c: &str) -> &str {
+    match c {
+        ">" => "<",
+        "<" => ">",
+        "(" => ")",
+        ")" => "(",
+        "[" => "]",
+        "]" => "[",
+        "{" => "}",
+        "}" => "{",
+        "/" => "\\",
+        "\\" => "/",
+        "▌" => "▐",
+        "▐" => "▌",
+        "╸" => "╺",
+        "╺" => "╸",
+        _ => c,
+    }
+}
+
+fn checked_style<S>(style: S) -> String
+where
+    S: Into<String>,
+{
+    let style = style.into();
+    if let Some(name) = style.strip_prefix(Preset::STYLE_PREFIX) {
+        match name.parse::<Preset>() {
+            Ok(preset) => return preset.into(),
+            Err(_) => warn!("Unknown style-preset {:?}, e.g. preset:blocks", name),
+        }
+    }
+
+    if style.chars().count() != 5 {
+        warn!("The bar-style has to consist of 5 characters, e.g. [=>-]");
+    };
+    style
 }
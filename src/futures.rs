@@ -0,0 +1,156 @@
+//! Driving bars by streams and futures (feature `futures`), independent of any specific runtime.
+//!
+//! Bars are shared via `Arc<Mutex<_>>`, such that they can be printed elsewhere, e.g. by a `ticking::Ticker`.
+
+use crate::{bernoulli, Baring};
+use futures_core::{Future, Stream};
+use futures_util::stream::FuturesUnordered;
+use pin_project_lite::pin_project;
+use std::{
+    pin::Pin,
+    sync::{Arc, Mutex, PoisonError},
+    task::{Context, Poll},
+};
+
+pin_project! {
+    /// A stream adding `1` to the given bar for every yielded item, created by `ProgressStreamExt::progress(...)`.
+    ///
+    /// The bar is finished when the stream ends.
+    #[derive(Debug)]
+    pub struct ProgressStream<S, B> {
+        #[pin]
+        stream: S,
+        bar: Arc<Mutex<B>>,
+    }
+}
+
+impl<S, B> Stream for ProgressStream<S, B>
+where
+    S: Stream,
+    B: Baring<Progress = usize>,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<S::Item>> {
+        let this = self.project();
+        let item = this.stream.poll_next(cx);
+        if let Poll::Ready(item) = &item {
+            let mut bar = this.bar.lock().unwrap_or_else(PoisonError::into_inner);
+            match item {
+                Some(_) => bar.add(1usize),
+                None if !bar.is_done() => bar.finish(),
+                None => (),
+            }
+        }
+        item
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
+
+/// Adds `progress(...)` to all streams, in the style of `StreamExt`.
+///
+///
+/// # Mini-Example
+///
+/// ```
+/// use futures_util::{stream, StreamExt};
+/// use progressing::{futures::ProgressStreamExt, mapping::Bar as MappingBar, Baring};
+/// use std::sync::{Arc, Mutex};
+///
+/// fn main() {
+///     let progress_bar = Arc::new(Mutex::new(MappingBar::with_range(0usize, 10)));
+///     let items = stream::iter(0..10).progress(Arc::clone(&progress_bar));
+///
+///     let sum: usize = futures_executor::block_on(items.fold(0, |sum, i| async move { sum + i }));
+///     assert_eq!(sum, 45);
+///     assert_eq!(progress_bar.lock().unwrap().progress(), 10);
+///     assert!(progress_bar.lock().unwrap().is_done());
+/// }
+/// ```
+pub trait ProgressStreamExt: Stream + Sized {
+    /// Adds `1` to the given bar for every item yielded by this stream.
+    fn progress<B>(self, bar: Arc<Mutex<B>>) -> ProgressStream<Self, B>
+    where
+        B: Baring<Progress = usize>,
+    {
+        ProgressStream { stream: self, bar }
+    }
+}
+
+impl<S> ProgressStreamExt for S where S: Stream {}
+
+/// A stream of the outputs of the given futures in the order of their completion,
+/// counting every `Ok` as success and every `Err` as failed attempt of the given `bernoulli::Bar`.
+///
+/// The bar is finished when all futures are completed.
+///
+///
+/// # Mini-Example
+///
+/// ```
+/// use futures_util::StreamExt;
+/// use progressing::{bernoulli::Bar as BernoulliBar, futures::Attempts, Baring};
+/// use std::sync::{Arc, Mutex};
+///
+/// fn main() {
+///     let progress_bar = Arc::new(Mutex::new(BernoulliBar::with_goal(10)));
+///     let jobs = (0..15).map(|i| async move { if i % 3 == 0 { Err(i) } else { Ok(i) } });
+///
+///     let outputs: Vec<_> = futures_executor::block_on(
+///         Attempts::with(jobs, Arc::clone(&progress_bar)).collect(),
+///     );
+///     assert_eq!(outputs.len(), 15);
+///
+///     let progress = progress_bar.lock().unwrap().progress();
+///     assert_eq!((progress.successes, progress.attempts), (10, 15));
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Attempts<F, B> {
+    futures: FuturesUnordered<F>,
+    bar: Arc<Mutex<B>>,
+}
+
+impl<F, B> Attempts<F, B>
+where
+    F: Future,
+    B: Baring<Progress = bernoulli::Progress>,
+{
+    pub fn with<I>(futures: I, bar: Arc<Mutex<B>>) -> Attempts<F, B>
+    where
+        I: IntoIterator<Item = F>,
+    {
+        Attempts {
+            futures: futures.into_iter().collect(),
+            bar,
+        }
+    }
+}
+
+impl<F, B, T, E> Stream for Attempts<F, B>
+where
+    F: Future<Output = Result<T, E>>,
+    B: Baring<Progress = bernoulli::Progress>,
+{
+    type Item = Result<T, E>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<T, E>>> {
+        let output = Pin::new(&mut self.futures).poll_next(cx);
+        if let Poll::Ready(output) = &output {
+            let mut bar = self.bar.lock().unwrap_or_else(PoisonError::into_inner);
+            match output {
+                Some(result) => bar.add(result.is_ok()),
+                None if !bar.is_done() => bar.finish(),
+                None => (),
+            }
+        }
+        output
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.futures.size_hint()
+    }
+}
@@ -0,0 +1,273 @@
+//! A machine-readable stream of progress-events as JSON Lines (feature `json`).
+//!
+//! Every line is one JSON-object describing a bar at a significant progress, e.g.
+//!
+//! ```text
+//! {"id":"download","start":0,"position":42,"end":60,"fraction":0.7,"attempts":130,"elapsed_ms":1200,"eta_ms":514,"rate":35.0,"state":"Running"}
+//! ```
+//!
+//! A `Reader` parses such a stream, such that another process can reconstruct the bar via `Event::snapshot()`.
+
+use crate::{
+    snapshot::{Snapshot, Snapshotting},
+    State,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, Write},
+    marker::PhantomData,
+    time::Duration,
+};
+
+/// One progress-event, meaning one line of the stream.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Event<N> {
+    /// Identifies the bar in a stream of several bars
+    pub id: String,
+    pub start: N,
+    pub position: N,
+    pub end: N,
+    /// The progress mapped to `[0, 1]`
+    pub fraction: f64,
+    /// Only counted by bars counting attempts, e.g. `bernoulli::Bar`
+    pub attempts: Option<usize>,
+    /// Only measured by timed bars
+    pub elapsed_ms: Option<u64>,
+    /// Approximated remaining time, only known by running timed bars with some progress
+    pub eta_ms: Option<u64>,
+    /// Progress per second, only known by timed bars
+    pub rate: Option<f64>,
+    pub state: State,
+}
+
+impl<N> Event<N>
+where
+    N: Serialize,
+{
+    /// Creates the event describing the given bar.
+    pub fn from<B>(id: &str, bar: &B) -> Event<N>
+    where
+        B: Snapshotting<Value = N>,
+    {
+        let snapshot = bar.snapshot();
+        let fraction = bar.fraction();
+
+        let (eta_ms, rate) = match snapshot.elapsed {
+            Some(elapsed) => {
+                let elapsed_s = elapsed.as_secs_f64();
+                let eta_ms = if snapshot.state == State::Running && fraction > 0.0 {
+                    Some((elapsed_s * (1.0 - fraction) / fraction * 1_000.0) as u64)
+                } else {
+                    None
+                };
+                let rate = match (as_f64(&snapshot.start), as_f64(&snapshot.progress)) {
+                    (Some(start), Some(progress)) if elapsed_s > 0.0 => {
+                        Some((progress - start) / elapsed_s)
+                    }
+                    _ => None,
+                };
+                (eta_ms, rate)
+            }
+            None => (None, None),
+        };
+
+        Event {
+            id: String::from(id),
+            start: snapshot.start,
+            position: snapshot.progress,
+            end: snapshot.end,
+            fraction,
+            attempts: snapshot.attempts,
+            elapsed_ms: snapshot.elapsed.map(|elapsed| elapsed.as_millis() as u64),
+            eta_ms,
+            rate,
+            state: snapshot.state,
+        }
+    }
+}
+
+impl<N> Event<N>
+where
+    N: Clone,
+{
+    /// Returns the snapshot described by this event, which can be restored by a bar (see `Snapshotting::restore(...)`).
+    pub fn snapshot(&self) -> Snapshot<N> {
+        Snapshot {
+            start: self.start.clone(),
+            end: self.end.clone(),
+            progress: self.position.clone(),
+            attempts: self.attempts,
+            elapsed: self.elapsed_ms.map(Duration::from_millis),
+            state: self.state,
+            rates: Vec::new(),
+            sample_interval: None,
+        }
+    }
+}
+
+fn as_f64<N>(value: &N) -> Option<f64>
+where
+    N: Serialize,
+{
+    serde_json::to_value(value).ok()?.as_f64()
+}
+
+/// Writes progress-events of bars as JSON Lines.
+///
+/// The writer keeps its own record of every bar's progress, hence a bar can be printed (e.g. by a `Printer`) besides being streamed.
+///
+///
+/// # Mini-Example
+///
+/// ```
+/// use progressing::{json::Writer, mapping::Bar as MappingBar, printing::Printer, Baring};
+///
+/// fn main() -> std::io::Result<()> {
+///     let mut printer = Printer::stderr();
+///     let mut writer = Writer::with(Vec::new());
+///     let mut progress_bar = MappingBar::with_range(0usize, 100).timed();
+///
+///     for value in 0..=100usize {
+///         progress_bar.set(value);
+///         printer.print(&mut progress_bar)?;
+///         writer.write("count", &progress_bar)?;
+///     }
+///     progress_bar.finish();
+///     printer.print(&mut progress_bar)?;
+///     writer.write("count", &progress_bar)?;
+///
+///     // the first event, one per 10 %, and the finished bar
+///     let stream = String::from_utf8(writer.into_inner()).unwrap();
+///     assert_eq!(stream.lines().count(), 12);
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Writer<W>
+where
+    W: Write,
+{
+    out: W,
+    /// Every bar's fraction and state when its latest event has been written
+    last_events: HashMap<String, (f64, State)>,
+    interesting_fraction_step: f64,
+}
+
+impl<W> Writer<W>
+where
+    W: Write,
+{
+    pub fn with(out: W) -> Writer<W> {
+        Writer {
+            out,
+            last_events: HashMap::new(),
+            interesting_fraction_step: 0.1,
+        }
+    }
+
+    /// Sets the fraction of progress (e.g. `0.1` for every 10 %, by default), which is worth another event.
+    pub fn set_interesting_fraction_step(&mut self, step: f64) {
+        self.interesting_fraction_step = step;
+    }
+
+    /// Writes an event for the given bar, if needed.
+    ///
+    /// Events are written for the first call per id, for significant progress since the id's latest event (see `set_interesting_fraction_step(...)`) and for changed states (e.g. finishing).
+    pub fn write<B>(&mut self, id: &str, bar: &B) -> io::Result<()>
+    where
+        B: Snapshotting,
+        B::Value: Serialize,
+    {
+        let fraction = bar.fraction();
+        let state = bar.snapshot().state;
+        let is_needed = match self.last_events.get(id) {
+            Some(&(last_fraction, last_state)) => {
+                last_state != state || self.step(fraction) > self.step(last_fraction)
+            }
+            None => true,
+        };
+        if !is_needed {
+            return Ok(());
+        }
+
+        let event = Event::from(id, bar);
+        serde_json::to_writer(&mut self.out, &event)?;
+        writeln!(self.out)?;
+        self.last_events
+            .insert(String::from(id), (event.fraction, event.state));
+        self.out.flush()
+    }
+
+    /// Returns the number of interesting steps the given fraction has reached.
+    fn step(&self, fraction: f64) -> usize {
+        let scale = 1_000.0;
+        (fraction * scale) as usize / ((scale * self.interesting_fraction_step) as usize).max(1)
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+}
+
+/// Parses progress-events written by a `Writer`.
+///
+///
+/// # Mini-Example
+///
+/// ```
+/// use progressing::{json::Reader, mapping::Bar as MappingBar, snapshot::Snapshotting, Baring};
+///
+/// fn main() -> std::io::Result<()> {
+///     let stream = r#"{"id":"copy","start":0,"position":42,"end":60,"fraction":0.7,"attempts":null,"elapsed_ms":null,"eta_ms":null,"rate":null,"state":"Running"}"#;
+///
+///     let mut progress_bar = MappingBar::with_range(0usize, 60);
+///     for event in Reader::<_, usize>::with(stream.as_bytes()) {
+///         progress_bar.restore(&event?.snapshot());
+///     }
+///     assert_eq!(progress_bar.progress(), 42);
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Reader<R, N>
+where
+    R: BufRead,
+{
+    lines: io::Lines<R>,
+    _value: PhantomData<N>,
+}
+
+impl<R, N> Reader<R, N>
+where
+    R: BufRead,
+{
+    pub fn with(input: R) -> Reader<R, N> {
+        Reader {
+            lines: input.lines(),
+            _value: PhantomData,
+        }
+    }
+}
+
+impl<R, N> Iterator for Reader<R, N>
+where
+    R: BufRead,
+    N: DeserializeOwned,
+{
+    type Item = io::Result<Event<N>>;
+
+    /// Returns the next event, skipping empty lines.
+    fn next(&mut self) -> Option<io::Result<Event<N>>> {
+        loop {
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(e) => return Some(Err(e)),
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            return Some(serde_json::from_str(&line).map_err(io::Error::from));
+        }
+    }
+}
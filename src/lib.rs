@@ -1,12 +1,54 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
 pub mod bernoulli;
+#[cfg(feature = "alloc")]
 pub mod clamping;
+#[cfg(feature = "futures")]
+pub mod futures;
+#[cfg(feature = "json")]
+pub mod json;
+#[cfg(feature = "std")]
+pub mod logging;
+#[cfg(feature = "alloc")]
 pub mod mapping;
+#[cfg(feature = "alloc")]
+pub mod mini;
+#[cfg(feature = "std")]
+pub mod nesting;
+#[cfg(feature = "rayon")]
+pub mod parallel;
+#[cfg(feature = "std")]
+pub mod printing;
+#[cfg(feature = "std")]
+pub mod snapshot;
+#[cfg(feature = "std")]
+pub mod staging;
+pub mod terminal;
+#[cfg(feature = "std")]
+pub mod ticking;
+#[cfg(feature = "alloc")]
 pub mod timing;
+#[cfg(feature = "tracing")]
+pub mod tracing;
 
-use std::ops::Add;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use core::{
+    fmt::{self, Display},
+    ops::Add,
+    time::Duration,
+};
 
 /// A trait describing basic functionality for simple text-based progress-bars.
 ///
+/// Bars render via `Display` without allocating, hence writing them into a reused buffer, e.g. `write!(buffer, "{}", bar)`, doesn't allocate per frame.
+/// Numbers are padded to the width of their maximum value (e.g. `( 4/10)`) and approximated times to a fixed width, hence frames of a running bar have the same length.
+///
 ///
 /// # Mini-Examples
 ///
@@ -54,6 +96,8 @@ use std::ops::Add;
 ///     bernoulli();
 /// }
 /// ```
+#[cfg(feature = "alloc")]
+#[allow(clippy::len_without_is_empty)]
 pub trait Baring {
     type Progress: Add<Output = Self::Progress>;
 
@@ -64,6 +108,12 @@ pub trait Baring {
     /// `[========>-]` becomes `[====>]==>-]` instead of `[====>]     `.
     fn set_len(&mut self, new_bar_len: usize);
 
+    /// Lets the bar fill the terminal's columns left by the surrounding text, re-measured on every print.
+    /// If less than `min_bar_len` columns are left, only the percentage is printed instead of the bar.
+    ///
+    /// Calling `set_len(...)` fixes the length again.
+    fn fit_terminal(&mut self, min_bar_len: usize);
+
     fn progress(&self) -> Self::Progress;
 
     /// Sets the progress to the given value
@@ -86,4 +136,130 @@ pub trait Baring {
     fn has_progressed_significantly(&self) -> bool;
 
     fn remember_significant_progress(&mut self);
+
+    fn state(&self) -> State;
+
+    /// Sets the lifecycle-state of the bar.
+    /// Prefer `finish()`, `abandon()` or `fail()`, which are implemented based on this.
+    fn set_state(&mut self, new_state: State);
+
+    /// Sets the text printed in front of the bar, e.g. a task-name.
+    ///
+    /// The prefix is truncated with an ellipsis (`…`) if it is too long.
+    fn set_prefix<S>(&mut self, prefix: S)
+    where
+        S: Into<String>;
+
+    /// Sets the text printed after the bar, e.g. the currently processed file.
+    /// This doesn't change the progress, but a changed message counts as significant progress.
+    ///
+    /// The message is truncated with an ellipsis (`…`) if it is too long.
+    fn set_message<S>(&mut self, msg: S)
+    where
+        S: Into<String>;
+
+    /// Marks the bar as done successfully, hence its final frame shows a completely filled bar.
+    /// Further progress is ignored.
+    ///
+    ///
+    /// # Mini-Example
+    ///
+    /// ```
+    /// use progressing::{clamping::Bar as ClampingBar, mapping::Bar as MappingBar, Baring};
+    ///
+    /// fn main() {
+    ///     let mut progress_bar = MappingBar::with_range(0, 10);
+    ///     progress_bar.set_len(12);
+    ///     progress_bar.set(4);
+    ///     assert_eq!(progress_bar.to_string(), "[====>.....] ( 4/10)");
+    ///
+    ///     progress_bar.finish();
+    ///     progress_bar.set(5);
+    ///     assert_eq!(progress_bar.to_string(), "[==========] ( 4/10)");
+    ///
+    ///     // abandoned and failed bars show where they stopped
+    ///     let mut progress_bar = ClampingBar::new();
+    ///     progress_bar.set_len(12);
+    ///     progress_bar.set(0.4);
+    ///     progress_bar.abandon();
+    ///     assert_eq!(progress_bar.to_string(), "[====>.....]");
+    ///     progress_bar.fail();
+    ///     assert_eq!(progress_bar.to_string(), "[====X.....]");
+    /// }
+    /// ```
+    fn finish(&mut self) {
+        self.set_state(State::Finished);
+    }
+
+    /// Finishes the bar and shows the given message after it (in its final frame).
+    fn finish_with_message<S>(&mut self, msg: S)
+    where
+        S: Into<String>,
+    {
+        self.set_message(msg);
+        self.finish();
+    }
+
+    /// Marks the bar as stopped before reaching its end, e.g. because the job has been cancelled.
+    /// Further progress is ignored.
+    fn abandon(&mut self) {
+        self.set_state(State::Abandoned);
+    }
+
+    /// Marks the bar as failed.
+    /// Further progress is ignored.
+    fn fail(&mut self) {
+        self.set_state(State::Failed);
+    }
+
+    fn is_done(&self) -> bool {
+        self.state().is_done()
+    }
+
+    /// Moves animated effects (e.g. `clamping::Effect::Shimmer`) to their next frame, e.g. before every print.
+    /// A `ticking::Ticker` ticks before every print.
+    fn tick(&mut self) {}
+
+    /// Marks the bar as stalled or not, which shows `clamping::Effect::Pulse` while stalled and colored.
+    /// Timed bars detecting stalls (see `timing::Bar::set_stall_timeout(...)`) call this themselves.
+    fn set_stalled(&mut self, _is_stalled: bool) {}
+
+    /// Returns the time without progress once per stall, if the bar detects stalls (see `timing::Bar::set_stall_timeout(...)`).
+    ///
+    /// Reporting the stall may log a warning, hence call this without holding the lock of a shared `Printer`, e.g. after printing.
+    fn take_stall(&mut self) -> Option<Duration> {
+        None
+    }
+}
+
+/// Bars writing their line surrounded by further text, e.g. by the stage and the approximated time of a `staging::Bar`.
+///
+/// The surrounding text counts into fitting the terminal (see `Baring::fit_terminal(...)`), hence the whole line fits.
+#[cfg(feature = "alloc")]
+pub trait Surrounding {
+    /// Writes the line preceded by `lead` and followed by `trail`, e.g. `[2/3] Compiling [=====>....] (5/16) ~2s`.
+    fn fmt_surrounded<L, T>(&self, f: &mut fmt::Formatter<'_>, lead: L, trail: T) -> fmt::Result
+    where
+        L: Display,
+        T: Display;
+}
+
+/// The lifecycle of a bar.
+///
+/// Every bar starts `Running`.
+/// As soon as it is done (finished, abandoned or failed), further progress is ignored and the bar renders its final frame.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum State {
+    #[default]
+    Running,
+    Finished,
+    Abandoned,
+    Failed,
+}
+
+impl State {
+    pub fn is_done(&self) -> bool {
+        *self != State::Running
+    }
 }
@@ -0,0 +1,133 @@
+//! Integration of the `log`-crate, such that log-messages and bars can share a terminal.
+//!
+//! Every log-record is printed above the currently drawn bar, which is redrawn afterwards.
+//! Records logged while the printer itself is printing on the same thread (e.g. by a bar being rendered) are written to `stderr` directly, since the printer is busy.
+
+use crate::printing::{self, Printer};
+use log::{LevelFilter, Log, Metadata, Record, SetLoggerError};
+use std::{
+    io::Write,
+    sync::{Arc, Mutex, PoisonError, TryLockError},
+};
+
+/// A logger printing records above the bar of the given `Printer`.
+///
+/// Records are either printed by the logger itself (`Logger::new(...)`), or by another logger (`Logger::wrapping(...)`), e.g. from `env_logger`.
+/// While the record is printed, the bar is removed from the terminal.
+///
+///
+/// # Mini-Example
+///
+/// ```
+/// use log::{info, LevelFilter};
+/// use progressing::{logging::Logger, mapping::Bar as MappingBar, printing::Printer, Baring};
+///
+/// fn main() -> std::io::Result<()> {
+///     let printer = Printer::stderr().shared();
+///     Logger::new(printer.clone())
+///         .install(LevelFilter::Info)
+///         .expect("No other logger should be installed.");
+///
+///     let mut progress_bar = MappingBar::with_range(0, 100);
+///     progress_bar.set_len(20);
+///     for value in 0..=100 {
+///         progress_bar.set(value);
+///         printer.lock().unwrap().print(&mut progress_bar)?;
+///
+///         if value % 25 == 0 {
+///             info!("Reached {}", value);
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+pub struct Logger<W>
+where
+    W: Write + Send,
+{
+    inner: Option<Box<dyn Log>>,
+    printer: Arc<Mutex<Printer<W>>>,
+}
+
+impl<W> Logger<W>
+where
+    W: Write + Send,
+{
+    /// Creates a logger printing records like `[INFO] message` via the given printer.
+    pub fn new(printer: Arc<Mutex<Printer<W>>>) -> Logger<W> {
+        Logger {
+            inner: None,
+            printer,
+        }
+    }
+
+    /// Creates a logger passing records to the given logger, while the printer's bar is removed from the terminal.
+    pub fn wrapping<L>(inner: L, printer: Arc<Mutex<Printer<W>>>) -> Logger<W>
+    where
+        L: Log + 'static,
+    {
+        Logger {
+            inner: Some(Box::new(inner)),
+            printer,
+        }
+    }
+
+    /// Sets this logger as the global logger of the `log`-crate.
+    pub fn install(self, max_level: LevelFilter) -> Result<(), SetLoggerError>
+    where
+        W: 'static,
+    {
+        log::set_boxed_logger(Box::new(self))?;
+        log::set_max_level(max_level);
+        Ok(())
+    }
+}
+
+impl<W> Log for Logger<W>
+where
+    W: Write + Send,
+{
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        match &self.inner {
+            Some(inner) => inner.enabled(metadata),
+            None => metadata.level() <= log::max_level(),
+        }
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        // A poisoned printer is still able to print.
+        let mut printer = match self.printer.try_lock() {
+            Ok(printer) => printer,
+            Err(TryLockError::Poisoned(e)) => e.into_inner(),
+            // Waiting for a printer printing on this thread would deadlock.
+            Err(TryLockError::WouldBlock) if printing::is_printing() => {
+                match &self.inner {
+                    Some(inner) => inner.log(record),
+                    None => eprintln!("[{}] {}", record.level(), record.args()),
+                }
+                return;
+            }
+            Err(TryLockError::WouldBlock) => {
+                self.printer.lock().unwrap_or_else(PoisonError::into_inner)
+            }
+        };
+        // Logging must not panic, hence errors are dropped.
+        let _ = match &self.inner {
+            Some(inner) => printer.suspend(|_| {
+                inner.log(record);
+                inner.flush();
+            }),
+            None => printer.println(format!("[{}] {}", record.level(), record.args())),
+        };
+    }
+
+    fn flush(&self) {
+        if let Some(inner) = &self.inner {
+            inner.flush();
+        }
+    }
+}
@@ -1,12 +1,36 @@
-use crate::{clamping, timing, Baring};
-use std::fmt::{self, Display};
-
+use crate::{
+    clamping::{self, display_width, Effect, FmtFn, Orientation, Preset},
+    timing, Baring, State, Surrounding,
+};
+use alloc::string::String;
+use core::{
+    cmp::max,
+    fmt::{self, Display, Write},
+    time::Duration,
+};
+use log::warn;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Config<N> {
     pub bar_len: usize,
+    pub is_fitting_terminal: bool,
+    pub min_bar_len: usize,
     pub style: String,
+    pub finished_style: String,
+    pub failed_style: String,
     pub interesting_progress_step: f64,
+    pub max_prefix_len: usize,
+    pub max_message_len: usize,
+    pub orientation: Orientation,
+    pub is_labeled: bool,
+    pub effect: Effect,
     pub min_k: N,
     pub max_k: N,
+    pub format: Format,
+    pub notation: Notation,
+    /// Written after the numbers, e.g. `files`
+    pub unit: Option<String>,
 }
 
 impl<N> Config<N> {
@@ -16,10 +40,143 @@ impl<N> Config<N> {
 
         Config {
             bar_len: cfg.bar_len,
+            is_fitting_terminal: cfg.is_fitting_terminal,
+            min_bar_len: cfg.min_bar_len,
             style: cfg.style,
+            finished_style: cfg.finished_style,
+            failed_style: cfg.failed_style,
             interesting_progress_step: cfg.interesting_progress_step,
+            max_prefix_len: cfg.max_prefix_len,
+            max_message_len: cfg.max_message_len,
+            orientation: cfg.orientation,
+            is_labeled: cfg.is_labeled,
+            effect: cfg.effect,
             min_k,
             max_k,
+            format: Format::default(),
+            notation: Notation::default(),
+            unit: None,
+        }
+    }
+}
+
+impl<N> Default for Config<N>
+where
+    N: Default,
+{
+    fn default() -> Config<N> {
+        Config::with(N::default(), N::default())
+    }
+}
+
+/// What a `mapping::Bar` shows after the bar itself.
+///
+///
+/// # Mini-Example
+///
+/// ```
+/// use progressing::{
+///     mapping::{Bar as MappingBar, Format, Notation},
+///     Baring,
+/// };
+///
+/// fn main() {
+///     let mut progress_bar = MappingBar::with_range(0, 3_400_000);
+///     progress_bar.set_len(20);
+///     progress_bar.set(1_200_000);
+///
+///     progress_bar.set_notation(Notation::Si);
+///     progress_bar.set_unit("files");
+///     assert_eq!(progress_bar.to_string(), "[======>...........] (  1.2M/3.4M files)");
+///
+///     progress_bar.set_format(Format::Remaining);
+///     progress_bar.set_notation(Notation::Grouped);
+///     assert_eq!(progress_bar.to_string(), "[======>...........] (2,200,000 files left)");
+///
+///     progress_bar.set_format(Format::Percentage { decimals: 1 });
+///     assert_eq!(progress_bar.to_string(), "[======>...........]  35.3%");
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Format {
+    /// The progress and the end, e.g. `(42/60)`
+    #[default]
+    Fraction,
+    /// The progress in percent with the given number of decimals, e.g. `70.0%`.
+    /// The unit is not shown.
+    Percentage { decimals: usize },
+    /// The count left until the end, e.g. `(18 left)`
+    Remaining,
+}
+
+/// How a `mapping::Bar` writes its numbers.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Notation {
+    /// As given by `Display`, e.g. `1234567`
+    #[default]
+    Plain,
+    /// With thousands-separators, e.g. `1,234,567`
+    Grouped,
+    /// Scaled by SI-prefixes, e.g. `1.2M`
+    Si,
+    /// Scaled by byte-units, e.g. `512.0 MiB`.
+    /// The progress is padded to a stable width, and timed bars show the byte-rate.
+    Bytes(ByteUnits),
+}
+
+/// Units of `Notation::Bytes`
+///
+///
+/// # Mini-Example
+///
+/// ```
+/// use progressing::{
+///     mapping::{Bar as MappingBar, ByteUnits, Notation},
+///     Baring,
+/// };
+///
+/// fn main() {
+///     let mut progress_bar = MappingBar::with_range(0u64, 2 * 1024 * 1024 * 1024);
+///     progress_bar.set_len(20);
+///     progress_bar.set_notation(Notation::Bytes(ByteUnits::Iec));
+///
+///     progress_bar.set(512 * 1024 * 1024u64);
+///     assert_eq!(progress_bar.to_string(), "[====>.............] (512.0 MiB / 2.0 GiB)");
+///     progress_bar.set(42u64);
+///     assert_eq!(progress_bar.to_string(), "[>.................] (     42 B / 2.0 GiB)");
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ByteUnits {
+    /// Powers of `1024`, e.g. `KiB` or `MiB`
+    Iec,
+    /// Powers of `1000`, e.g. `kB` or `MB`
+    Si,
+}
+
+impl ByteUnits {
+    fn base(self) -> f64 {
+        match self {
+            ByteUnits::Iec => 1024.0,
+            ByteUnits::Si => 1000.0,
+        }
+    }
+
+    fn names(self) -> [&'static str; 7] {
+        match self {
+            ByteUnits::Iec => ["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"],
+            ByteUnits::Si => ["B", "kB", "MB", "GB", "TB", "PB", "EB"],
+        }
+    }
+
+    /// Returns the maximum width of a formatted count, e.g. `999.9 KiB`.
+    fn max_width(self) -> usize {
+        match self {
+            ByteUnits::Iec => 9,
+            ByteUnits::Si => 8,
         }
     }
 }
@@ -39,12 +196,65 @@ impl<N> Config<N> {
 ///     println!("{}", progress_bar);
 /// }
 /// ```
+///
+/// Rendering doesn't allocate, hence bars may be written into any `core::fmt::Write`, e.g. the buffer of a serial console, also without `std` (see feature `alloc`).
+///
+/// ```
+/// use core::fmt::{self, Write};
+/// use progressing::{mapping::Bar as MappingBar, Baring};
+///
+/// /// The buffer of a serial console, whose size is fixed
+/// struct Console {
+///     bytes: [u8; 64],
+///     len: usize,
+/// }
+///
+/// impl Write for Console {
+///     fn write_str(&mut self, s: &str) -> fmt::Result {
+///         let end = self.len + s.len();
+///         let free_bytes = self.bytes.get_mut(self.len..end).ok_or(fmt::Error)?;
+///         free_bytes.copy_from_slice(s.as_bytes());
+///         self.len = end;
+///         Ok(())
+///     }
+/// }
+///
+/// fn main() {
+///     let mut progress_bar = MappingBar::with_range(0, 60);
+///     progress_bar.set_len(20);
+///     progress_bar.set(42);
+///
+///     let mut console = Console { bytes: [0; 64], len: 0 };
+///     write!(console, "{}", progress_bar).unwrap();
+///     let line = core::str::from_utf8(&console.bytes[..console.len]).unwrap();
+///     assert_eq!(line, "[============>.....] (42/60)");
+/// }
+/// ```
 #[derive(Debug)]
 pub struct Bar<N> {
     pub(crate) bar: clamping::Bar,
     min_k: N,
     max_k: N,
     k: N,
+    format: Format,
+    notation: Notation,
+    unit: Option<String>,
+}
+
+impl<N> Surrounding for Bar<N>
+where
+    N: Display,
+    Bar<N>: Baring,
+    <Bar<N> as Baring>::Progress: Display,
+{
+    fn fmt_surrounded<L, T>(&self, f: &mut fmt::Formatter<'_>, lead: L, trail: T) -> fmt::Result
+    where
+        L: Display,
+        T: Display,
+    {
+        self.bar
+            .fmt_line_with(f, lead, format_args!("{}{}", self.suffix(), trail))
+    }
 }
 
 impl<N> Display for Bar<N>
@@ -54,7 +264,114 @@ where
     <Bar<N> as Baring>::Progress: Display,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{} ({}/{})", self.bar, self.k, self.end())
+        self.bar.fmt_line(f, self.suffix())
+    }
+}
+
+impl<N> Bar<N>
+where
+    N: Display,
+    Bar<N>: Baring,
+    <Bar<N> as Baring>::Progress: Display,
+{
+    /// Returns everything following the bar itself, e.g. ` (4/5)`, depending on the format.
+    pub(crate) fn suffix(&self) -> impl Display + '_ {
+        FmtFn(move |f| {
+            let width = self.width();
+            match self.format {
+                Format::Fraction => {
+                    let separator = match self.notation {
+                        Notation::Bytes(_) => " / ",
+                        _ => "/",
+                    };
+                    write!(
+                        f,
+                        " ({:>width$}{}{}",
+                        self.number(&self.k),
+                        separator,
+                        self.number(self.end()),
+                        width = width
+                    )?;
+                    self.fmt_unit(f)?;
+                    f.write_str(")")
+                }
+                Format::Percentage { decimals } => {
+                    // as wide as `100%` or e.g. `100.0%`
+                    let width = 3 + if decimals > 0 { decimals + 1 } else { 0 };
+                    let percentage = self.bar.progress() * 100.0;
+                    write!(f, " {:>width$.*}%", decimals, percentage, width = width)
+                }
+                Format::Remaining => {
+                    f.write_str(" (")?;
+                    match (parse_number(&self.k), parse_number(self.end())) {
+                        (Some(k), Some(end)) => {
+                            write!(f, "{:>width$}", self.number(end - k), width = width)?
+                        }
+                        _ => f.write_str("?")?,
+                    }
+                    self.fmt_unit(f)?;
+                    f.write_str(" left)")
+                }
+            }
+        })
+    }
+
+    /// Returns the progress per second, e.g. ` 12.3 MiB/s`, if the notation is `Notation::Bytes`.
+    pub(crate) fn rate(&self, elapsed: Duration) -> impl Display + '_ {
+        FmtFn(move |f| {
+            let units = match self.notation {
+                Notation::Bytes(units) => units,
+                _ => return Ok(()),
+            };
+            let elapsed_s = elapsed.as_secs_f64();
+            let rate = match (parse_number(&self.k), parse_number(self.start())) {
+                (Some(k), Some(start)) if elapsed_s > 0.0 => (k - start) / elapsed_s,
+                _ => 0.0,
+            };
+            write!(
+                f,
+                " {:>width$}/s",
+                self.number(rate),
+                width = units.max_width()
+            )
+        })
+    }
+
+    fn number<M>(&self, value: M) -> Number<M> {
+        Number {
+            value,
+            notation: self.notation,
+        }
+    }
+
+    /// Returns the width numbers are padded to, such that every frame has the same length.
+    pub(crate) fn width(&self) -> usize {
+        let ends_width = max(
+            display_width(self.number(self.start())),
+            display_width(self.number(self.end())),
+        );
+        match self.notation {
+            Notation::Bytes(units) => units.max_width(),
+            Notation::Si => {
+                // scaled values in between may be wider than the ends, e.g. `999.9k` vs `3.4M`
+                let start = parse_number(self.start()).unwrap_or(0.0);
+                let end = parse_number(self.end()).unwrap_or(0.0);
+                if start.abs().max(end.abs()) < 999.95 {
+                    ends_width
+                } else {
+                    let sign_width = if start.min(end) < 0.0 { 1 } else { 0 };
+                    max(ends_width, SI_MAX_WIDTH + sign_width)
+                }
+            }
+            _ => ends_width,
+        }
+    }
+
+    fn fmt_unit(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.unit {
+            Some(unit) => write!(f, " {}", unit),
+            None => Ok(()),
+        }
     }
 }
 
@@ -68,6 +385,9 @@ where
             min_k: from.clone(),
             max_k: to,
             k: from,
+            format: Format::default(),
+            notation: Notation::default(),
+            unit: None,
         }
     }
 
@@ -75,156 +395,355 @@ where
         Bar {
             bar: clamping::Bar::with(clamping::Config {
                 bar_len: cfg.bar_len,
+                is_fitting_terminal: cfg.is_fitting_terminal,
+                min_bar_len: cfg.min_bar_len,
                 style: cfg.style,
+                finished_style: cfg.finished_style,
+                failed_style: cfg.failed_style,
                 interesting_progress_step: cfg.interesting_progress_step,
+                max_prefix_len: cfg.max_prefix_len,
+                max_message_len: cfg.max_message_len,
+                orientation: cfg.orientation,
+                is_labeled: cfg.is_labeled,
+                effect: cfg.effect,
             }),
             min_k: cfg.min_k.clone(),
             max_k: cfg.max_k,
             k: cfg.min_k,
+            format: cfg.format,
+            notation: cfg.notation,
+            unit: cfg.unit,
         }
     }
 
+    #[cfg(feature = "std")]
     pub fn timed(self) -> timing::Bar<Bar<N>>
     where
         Bar<N>: Baring,
     {
-        timing::Bar::with(self)
+        self.timed_by(timing::std_clock)
     }
-}
-
-impl Baring for Bar<usize> {
-    type Progress = usize;
 
-    fn len(&self) -> usize {
-        self.bar.len()
+    /// Measures time by the given clock, returning the time since an arbitrary, but fixed point in time (e.g. a hardware-timer).
+    pub fn timed_by(self, clock: fn() -> Duration) -> timing::Bar<Bar<N>>
+    where
+        Bar<N>: Baring,
+    {
+        timing::Bar::with(self, clock, |bar| bar.bar.progress())
     }
 
-    fn set_len(&mut self, new_bar_len: usize) {
-        self.bar.set_len(new_bar_len)
+    pub fn set_style<S>(&mut self, style: S)
+    where
+        S: Into<String>,
+    {
+        self.bar.set_style(style)
     }
 
-    fn progress(&self) -> usize {
-        self.k
+    /// Sets the style to the given preset (see `clamping::Bar::set_preset(...)`).
+    pub fn set_preset(&mut self, preset: Preset) {
+        self.bar.set_preset(preset)
     }
 
-    fn set<P>(&mut self, new_progress: P)
+    pub fn set_finished_style<S>(&mut self, style: S)
     where
-        P: Into<usize>,
+        S: Into<String>,
     {
-        let new_progress = new_progress.into();
-        self.k = new_progress;
-
-        // calculate new progress
-        let delta = new_progress - self.start();
-        let max_delta = self.end() - self.start();
-        self.bar.set(delta as f64 / (max_delta as f64));
+        self.bar.set_finished_style(style)
     }
 
-    fn start(&self) -> usize {
-        self.min_k
+    pub fn set_failed_style<S>(&mut self, style: S)
+    where
+        S: Into<String>,
+    {
+        self.bar.set_failed_style(style)
     }
 
-    fn end(&self) -> usize {
-        self.max_k
+    pub fn set_orientation(&mut self, orientation: Orientation) {
+        self.bar.set_orientation(orientation)
     }
 
-    fn has_progressed_significantly(&self) -> bool {
-        self.bar.has_progressed_significantly()
+    /// Shows the percentage centered inside the bar (see `clamping::Bar::set_labeled(...)`).
+    pub fn set_labeled(&mut self, is_labeled: bool) {
+        self.bar.set_labeled(is_labeled)
     }
 
-    fn remember_significant_progress(&mut self) {
-        self.bar.remember_significant_progress()
+    /// Sets the colored effect of the running bar (see `clamping::Effect`).
+    pub fn set_effect(&mut self, effect: Effect) {
+        self.bar.set_effect(effect)
     }
-}
-
-impl Baring for Bar<i64> {
-    type Progress = i64;
 
-    fn len(&self) -> usize {
-        self.bar.len()
+    /// Enables or disables colors (see `clamping::Bar::set_colored(...)`).
+    pub fn set_colored(&mut self, is_colored: bool) {
+        self.bar.set_colored(is_colored)
     }
 
-    fn set_len(&mut self, new_bar_len: usize) {
-        self.bar.set_len(new_bar_len)
+    pub fn set_format(&mut self, format: Format) {
+        self.format = format;
     }
 
-    fn progress(&self) -> i64 {
-        self.k
+    pub fn set_notation(&mut self, notation: Notation) {
+        self.notation = notation;
     }
 
-    fn set<P>(&mut self, new_progress: P)
+    /// Sets the unit written after the numbers, e.g. `files`.
+    /// An empty unit removes it.
+    pub fn set_unit<S>(&mut self, unit: S)
     where
-        P: Into<i64>,
+        S: Into<String>,
     {
-        let new_progress = new_progress.into();
-        self.k = new_progress;
-
-        // calculate new progress
-        // guaranteed to be positive or 0
-        let delta = (new_progress - self.start()) as usize;
-        let max_delta = (self.end() - self.start()) as usize;
-        self.bar.set(delta as f64 / (max_delta as f64));
+        self.unit = Some(unit.into()).filter(|unit| !unit.is_empty());
     }
+}
 
-    fn start(&self) -> i64 {
-        self.min_k
+/// Implements `Baring` for bars counting the given integer types.
+macro_rules! impl_baring {
+    ($($N:ty),*) => {$(
+        impl Baring for Bar<$N> {
+            type Progress = $N;
+
+            fn len(&self) -> usize {
+                self.bar.len()
+            }
+
+            fn set_len(&mut self, new_bar_len: usize) {
+                self.bar.set_len(new_bar_len)
+            }
+
+            fn fit_terminal(&mut self, min_bar_len: usize) {
+                self.bar.fit_terminal(min_bar_len)
+            }
+
+            fn progress(&self) -> $N {
+                self.k
+            }
+
+            fn set<P>(&mut self, new_progress: P)
+            where
+                P: Into<$N>,
+            {
+                if self.is_done() {
+                    warn!(
+                        "The bar is already done ({:?}), hence new progress is ignored.",
+                        self.state()
+                    );
+                    return;
+                }
+
+                let new_progress = new_progress.into();
+                self.k = new_progress;
+
+                // calculate new progress
+                // saturating, since progress below the start or wide ranges would overflow
+                let delta = new_progress.max(self.start()).saturating_sub(self.start());
+                let max_delta = self.end().saturating_sub(self.start());
+                self.bar.set(delta as f64 / (max_delta as f64));
+            }
+
+            fn start(&self) -> $N {
+                self.min_k
+            }
+
+            fn end(&self) -> $N {
+                self.max_k
+            }
+
+            fn has_progressed_significantly(&self) -> bool {
+                self.bar.has_progressed_significantly()
+            }
+
+            fn remember_significant_progress(&mut self) {
+                self.bar.remember_significant_progress()
+            }
+
+            fn state(&self) -> State {
+                self.bar.state()
+            }
+
+            fn set_state(&mut self, new_state: State) {
+                self.bar.set_state(new_state)
+            }
+
+            fn set_prefix<S>(&mut self, prefix: S)
+            where
+                S: Into<String>,
+            {
+                self.bar.set_prefix(prefix)
+            }
+
+            fn set_message<S>(&mut self, msg: S)
+            where
+                S: Into<String>,
+            {
+                self.bar.set_message(msg)
+            }
+
+            fn tick(&mut self) {
+                self.bar.tick()
+            }
+
+            fn set_stalled(&mut self, is_stalled: bool) {
+                self.bar.set_stalled(is_stalled)
+            }
+        }
+    )*};
+}
+
+impl_baring!(usize, u64, i64, i32);
+
+/// A stack-buffer for the displayed digits of a number, hence numbers are formatted without allocating.
+struct Digits {
+    bytes: [u8; 64],
+    len: usize,
+}
+
+impl Digits {
+    fn empty() -> Digits {
+        Digits {
+            bytes: [0; 64],
+            len: 0,
+        }
     }
 
-    fn end(&self) -> i64 {
-        self.max_k
+    /// Returns the displayed number, or `None` if it doesn't fit into the buffer.
+    fn of<N>(number: N) -> Option<Digits>
+    where
+        N: Display,
+    {
+        let mut digits = Digits::empty();
+        write!(digits, "{}", number).ok()?;
+        Some(digits)
     }
 
-    fn has_progressed_significantly(&self) -> bool {
-        self.bar.has_progressed_significantly()
+    fn as_str(&self) -> &str {
+        // only whole strs are written
+        core::str::from_utf8(&self.bytes[..self.len]).unwrap_or_default()
     }
+}
 
-    fn remember_significant_progress(&mut self) {
-        self.bar.remember_significant_progress()
+impl fmt::Write for Digits {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let new_len = self.len + s.len();
+        if new_len > self.bytes.len() {
+            return Err(fmt::Error);
+        }
+        self.bytes[self.len..new_len].copy_from_slice(s.as_bytes());
+        self.len = new_len;
+        Ok(())
     }
 }
 
-impl Baring for Bar<i32> {
-    type Progress = i32;
+/// A number written in the given notation, padded according to the formatter (e.g. `{:>9}`).
+struct Number<N> {
+    value: N,
+    notation: Notation,
+}
 
-    fn len(&self) -> usize {
-        self.bar.len()
+impl<N> Display for Number<N>
+where
+    N: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut buffer = Digits::empty();
+        let number = FmtFn(|f| fmt_number(f, &self.value, self.notation));
+        match write!(buffer, "{}", number) {
+            Ok(()) => f.pad(buffer.as_str()),
+            Err(_) => write!(f, "{}", number),
+        }
     }
+}
 
-    fn set_len(&mut self, new_bar_len: usize) {
-        self.bar.set_len(new_bar_len)
-    }
+/// Parses the displayed number, e.g. for calculating the remaining count of any number-type.
+fn parse_number<N>(number: N) -> Option<f64>
+where
+    N: Display,
+{
+    Digits::of(number)?.as_str().parse().ok()
+}
 
-    fn progress(&self) -> i32 {
-        self.k
+/// Writes the number in the given notation.
+fn fmt_number<N>(f: &mut fmt::Formatter<'_>, number: N, notation: Notation) -> fmt::Result
+where
+    N: Display,
+{
+    let digits = match Digits::of(&number) {
+        Some(digits) => digits,
+        None => return write!(f, "{}", number),
+    };
+    let digits = digits.as_str();
+
+    match notation {
+        Notation::Plain => f.write_str(digits),
+        Notation::Grouped => fmt_grouped(f, digits),
+        Notation::Si => match digits.parse::<f64>() {
+            Ok(value) => fmt_si(f, value, digits),
+            Err(_) => f.write_str(digits),
+        },
+        Notation::Bytes(units) => match digits.parse::<f64>() {
+            Ok(value) => fmt_bytes(f, value, units),
+            Err(_) => f.write_str(digits),
+        },
     }
+}
 
-    fn set<P>(&mut self, new_progress: P)
-    where
-        P: Into<i32>,
-    {
-        let new_progress = new_progress.into();
-        self.k = new_progress;
-
-        // calculate new progress
-        // guaranteed to be positive or 0
-        let delta = (new_progress - self.start()) as usize;
-        let max_delta = (self.end() - self.start()) as usize;
-        self.bar.set(delta as f64 / (max_delta as f64));
+/// Writes the digits with thousands-separators, e.g. `-1,234,567.89`.
+fn fmt_grouped(f: &mut fmt::Formatter<'_>, digits: &str) -> fmt::Result {
+    let (sign, unsigned) = match digits.strip_prefix('-') {
+        Some(unsigned) => ("-", unsigned),
+        None => ("", digits),
+    };
+    let int_len = unsigned
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(unsigned.len());
+    let (int, rest) = unsigned.split_at(int_len);
+
+    f.write_str(sign)?;
+    for (i, c) in int.chars().enumerate() {
+        if i > 0 && (int_len - i) % 3 == 0 {
+            f.write_str(",")?;
+        }
+        write!(f, "{}", c)?;
     }
+    f.write_str(rest)
+}
 
-    fn start(&self) -> i32 {
-        self.min_k
+/// Widest positive number scaled by SI-prefixes, e.g. `999.9k`
+const SI_MAX_WIDTH: usize = 6;
+
+/// Writes the value scaled by SI-prefixes with one decimal, e.g. `1.2M`.
+/// Values below `1000` are written as displayed.
+fn fmt_si(f: &mut fmt::Formatter<'_>, value: f64, digits: &str) -> fmt::Result {
+    let mut scaled = value;
+    let mut prefix = None;
+    for next_prefix in ["k", "M", "G", "T", "P", "E"] {
+        // rounding to one decimal must not lead to e.g. `1000.0k`
+        if scaled.abs() < 999.95 {
+            break;
+        }
+        scaled /= 1000.0;
+        prefix = Some(next_prefix);
     }
 
-    fn end(&self) -> i32 {
-        self.max_k
+    match prefix {
+        Some(prefix) => write!(f, "{:.1}{}", scaled, prefix),
+        None => f.write_str(digits),
     }
+}
 
-    fn has_progressed_significantly(&self) -> bool {
-        self.bar.has_progressed_significantly()
+/// Writes the value scaled by byte-units, e.g. `512.0 MiB`.
+/// Whole bytes are written without decimals, e.g. `512 B`.
+fn fmt_bytes(f: &mut fmt::Formatter<'_>, value: f64, units: ByteUnits) -> fmt::Result {
+    let names = units.names();
+    let mut scaled = value;
+    let mut i = 0;
+    // rounding to one decimal must not lead to e.g. `1000.0 KiB`
+    while scaled.abs() >= 999.95 && i + 1 < names.len() {
+        scaled /= units.base();
+        i += 1;
     }
 
-    fn remember_significant_progress(&mut self) {
-        self.bar.remember_significant_progress()
+    if i == 0 {
+        write!(f, "{:.0} {}", value, names[i])
+    } else {
+        write!(f, "{:.1} {}", scaled, names[i])
     }
 }
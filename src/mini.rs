@@ -0,0 +1,83 @@
+//! Renderings of a single char per value, e.g. for compact status panels showing many bars.
+//!
+//! They only read a bar, hence progress is updated as usual.
+
+#[cfg(feature = "std")]
+use crate::snapshot::Snapshotting;
+use crate::{clamping::FmtFn, timing, Baring};
+use core::fmt::{Display, Write};
+
+/// Blocks of increasing height, used for values in `[0, 1]`
+pub const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Returns the block showing the given value, clamped to `[0, 1]`.
+pub fn block(value: f64) -> char {
+    let value = if value.is_nan() { 0.0 } else { value };
+    // rounded, while `f64::round` needs std
+    let i = (value.clamp(0.0, 1.0) * (BLOCKS.len() - 1) as f64 + 0.5) as usize;
+    BLOCKS[i]
+}
+
+/// Returns a single block showing the bar's fraction, e.g. `▅` for a bar at 50 %.
+///
+///
+/// # Mini-Example
+///
+/// ```
+/// use progressing::{mapping::Bar as MappingBar, mini, Baring};
+///
+/// fn main() {
+///     let mut progress_bar = MappingBar::with_range(0, 60);
+///     progress_bar.set(30);
+///     assert_eq!(mini::vertical(&progress_bar), '▅');
+/// }
+/// ```
+#[cfg(feature = "std")]
+pub fn vertical<B>(bar: &B) -> char
+where
+    B: Snapshotting,
+{
+    block(bar.fraction())
+}
+
+/// Returns a sparkline of the bar's recent rates (see `timing::Bar::rates()`), scaled to the highest rate.
+///
+///
+/// # Mini-Example
+///
+/// ```
+/// use progressing::{mapping::Bar as MappingBar, mini, Baring};
+/// use std::{
+///     sync::atomic::{AtomicU64, Ordering},
+///     time::Duration,
+/// };
+///
+/// static NOW_MS: AtomicU64 = AtomicU64::new(0);
+///
+/// fn clock() -> Duration {
+///     Duration::from_millis(NOW_MS.load(Ordering::Relaxed))
+/// }
+///
+/// fn main() {
+///     let mut progress_bar = MappingBar::with_range(0, 100).timed_by(clock);
+///     for value in [10, 50, 80] {
+///         NOW_MS.fetch_add(1_000, Ordering::Relaxed);
+///         progress_bar.set(value);
+///     }
+///     assert_eq!(mini::sparkline(&progress_bar).to_string(), "▃█▆");
+/// }
+/// ```
+pub fn sparkline<B>(bar: &timing::Bar<B>) -> impl Display + '_
+where
+    B: Baring,
+{
+    FmtFn(move |f| {
+        let rates = bar.rates();
+        let max_rate = rates.iter().cloned().fold(0.0, f64::max);
+        for &rate in rates {
+            let value = if max_rate > 0.0 { rate / max_rate } else { 0.0 };
+            f.write_char(block(value))?;
+        }
+        Ok(())
+    })
+}
@@ -0,0 +1,415 @@
+//! A progress-bar composed of weighted child-bars, e.g. for builds with several phases.
+
+use crate::{clamping, snapshot::Snapshotting, timing, Baring, State, Surrounding};
+use log::warn;
+use std::{
+    any::Any,
+    fmt::{self, Display, Write},
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    time::Duration,
+};
+
+/// Bars usable as children of a `nesting::Bar`.
+///
+/// This is implemented for every bar providing snapshots, including `nesting::Bar` itself.
+/// The methods are prefixed to not collide with `Baring` and `Snapshotting`.
+pub trait Nestable: Display + Any {
+    /// Returns the progress mapped to `[0, 1]`.
+    fn nested_fraction(&self) -> f64;
+
+    fn nested_state(&self) -> State;
+
+    fn nested_has_progressed_significantly(&self) -> bool;
+
+    fn nested_remember_significant_progress(&mut self);
+
+    fn as_any(&self) -> &dyn Any;
+
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<B> Nestable for B
+where
+    B: Baring + Snapshotting + Display + Any,
+{
+    fn nested_fraction(&self) -> f64 {
+        self.fraction()
+    }
+
+    fn nested_state(&self) -> State {
+        self.state()
+    }
+
+    fn nested_has_progressed_significantly(&self) -> bool {
+        self.has_progressed_significantly()
+    }
+
+    fn nested_remember_significant_progress(&mut self) {
+        self.remember_significant_progress()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// How a `nesting::Bar` is printed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Layout {
+    /// One line showing the weighted progress of all children
+    Aggregate,
+    /// The aggregated line followed by every child in its own, indented line
+    Tree,
+}
+
+struct Child {
+    weight: f64,
+    bar: Box<dyn Nestable>,
+}
+
+/// Identifies a child of a `nesting::Bar` and knows its type.
+pub struct ChildId<B> {
+    idx: usize,
+    _bar: PhantomData<B>,
+}
+
+impl<B> Clone for ChildId<B> {
+    fn clone(&self) -> ChildId<B> {
+        *self
+    }
+}
+
+impl<B> Copy for ChildId<B> {}
+
+/// Gives access to a child and updates its parent when dropped.
+pub struct ChildGuard<'a, B>
+where
+    B: Nestable,
+{
+    parent: &'a mut Bar,
+    idx: usize,
+    _bar: PhantomData<B>,
+}
+
+impl<'a, B> Deref for ChildGuard<'a, B>
+where
+    B: Nestable,
+{
+    type Target = B;
+
+    fn deref(&self) -> &B {
+        self.parent.child(ChildId {
+            idx: self.idx,
+            _bar: PhantomData,
+        })
+    }
+}
+
+impl<'a, B> DerefMut for ChildGuard<'a, B>
+where
+    B: Nestable,
+{
+    fn deref_mut(&mut self) -> &mut B {
+        self.parent.children[self.idx]
+            .bar
+            .as_any_mut()
+            .downcast_mut()
+            .expect("The child's type is known by its id.")
+    }
+}
+
+impl<'a, B> Drop for ChildGuard<'a, B>
+where
+    B: Nestable,
+{
+    fn drop(&mut self) {
+        self.parent.update();
+    }
+}
+
+/// A progress-bar, whose progress is the weighted progress of its children.
+///
+/// Children may be bars of any type, e.g. `mapping::Bar` or even `nesting::Bar`.
+/// A failing child fails the parent, and the parent is finished (or abandoned) when all children are done.
+///
+///
+/// # Mini-Example
+///
+/// ```
+/// use progressing::{
+///     clamping::Bar as ClampingBar,
+///     mapping::Bar as MappingBar,
+///     nesting::{Bar as NestingBar, Layout},
+///     Baring,
+/// };
+///
+/// /// Build with phases download (20 %), compile (70 %) and link (10 %)
+/// /// [========>.........]  49%
+/// ///   download [========================================] (100/100)
+/// ///   compile [================>.......................] ( 5/12)
+/// ///   link [>.......................................]
+/// fn main() {
+///     let mut build = NestingBar::new();
+///     build.set_len(20);
+///     build.set_layout(Layout::Tree);
+///     let download = build.add_child("download", 0.2, MappingBar::with_range(0, 100));
+///     let compile = build.add_child("compile", 0.7, MappingBar::with_range(0, 12));
+///     let _link = build.add_child("link", 0.1, ClampingBar::new());
+///
+///     build.child_mut(download).set(100);
+///     build.child_mut(download).finish();
+///     build.child_mut(compile).set(5);
+///     println!("{}", build);
+/// }
+/// ```
+pub struct Bar {
+    bar: clamping::Bar,
+    children: Vec<Child>,
+    layout: Layout,
+}
+
+impl Bar {
+    pub fn new() -> Bar {
+        Bar::default()
+    }
+
+    pub fn with(cfg: clamping::Config) -> Bar {
+        Bar {
+            bar: clamping::Bar::with(cfg),
+            children: Vec::new(),
+            layout: Layout::Aggregate,
+        }
+    }
+
+    #[cfg(feature = "std")]
+    pub fn timed(self) -> timing::Bar<Bar> {
+        self.timed_by(timing::std_clock)
+    }
+
+    /// Measures time by the given clock, returning the time since an arbitrary, but fixed point in time (e.g. a hardware-timer).
+    pub fn timed_by(self, clock: fn() -> Duration) -> timing::Bar<Bar> {
+        timing::Bar::with(self, clock, |bar| bar.progress())
+    }
+
+    pub fn set_layout(&mut self, layout: Layout) {
+        self.layout = layout;
+    }
+
+    /// Adds the given bar as child, with `name` as its prefix.
+    /// Weights are relative to the sum of all weights.
+    pub fn add_child<S, B>(&mut self, name: S, weight: f64, mut bar: B) -> ChildId<B>
+    where
+        S: Into<String>,
+        B: Baring + Nestable,
+    {
+        bar.set_prefix(name);
+        self.children.push(Child {
+            weight,
+            bar: Box::new(bar),
+        });
+        self.update();
+
+        ChildId {
+            idx: self.children.len() - 1,
+            _bar: PhantomData,
+        }
+    }
+
+    pub fn child<B>(&self, id: ChildId<B>) -> &B
+    where
+        B: Nestable,
+    {
+        self.children[id.idx]
+            .bar
+            .as_any()
+            .downcast_ref()
+            .expect("The child's type is known by its id.")
+    }
+
+    /// Gives access to the child, e.g. for setting its progress.
+    /// The parent is updated when the returned guard is dropped.
+    pub fn child_mut<B>(&mut self, id: ChildId<B>) -> ChildGuard<'_, B>
+    where
+        B: Nestable,
+    {
+        ChildGuard {
+            parent: self,
+            idx: id.idx,
+            _bar: PhantomData,
+        }
+    }
+
+    fn weighted_fraction(&self) -> f64 {
+        let total_weight: f64 = self.children.iter().map(|child| child.weight).sum();
+        if total_weight > 0.0 {
+            self.children
+                .iter()
+                .map(|child| child.weight * child.bar.nested_fraction())
+                .sum::<f64>()
+                / total_weight
+        } else {
+            0.0
+        }
+    }
+
+    /// Takes the children's progress and states, unless this bar is already done.
+    fn update(&mut self) {
+        if self.bar.is_done() {
+            return;
+        }
+        self.bar.set(self.weighted_fraction());
+
+        // failing dominates running, which dominates abandoning, which dominates finishing
+        let merged_state = self
+            .children
+            .iter()
+            .map(|child| child.bar.nested_state())
+            .fold(None, |merged_state, state| {
+                Some(match (merged_state, state) {
+                    (Some(State::Failed), _) | (_, State::Failed) => State::Failed,
+                    (Some(State::Running), _) | (_, State::Running) => State::Running,
+                    (Some(State::Abandoned), _) | (_, State::Abandoned) => State::Abandoned,
+                    _ => State::Finished,
+                })
+            });
+        if let Some(merged_state) = merged_state.filter(State::is_done) {
+            self.bar.set_state(merged_state);
+        }
+    }
+}
+
+impl Default for Bar {
+    fn default() -> Bar {
+        Bar::with(clamping::Config::default())
+    }
+}
+
+impl Baring for Bar {
+    type Progress = f64;
+
+    fn len(&self) -> usize {
+        self.bar.len()
+    }
+
+    fn set_len(&mut self, new_bar_len: usize) {
+        self.bar.set_len(new_bar_len)
+    }
+
+    fn fit_terminal(&mut self, min_bar_len: usize) {
+        self.bar.fit_terminal(min_bar_len)
+    }
+
+    fn progress(&self) -> f64 {
+        self.bar.progress()
+    }
+
+    /// The progress is given by the children, hence setting it is ignored.
+    fn set<P>(&mut self, _new_progress: P)
+    where
+        P: Into<f64>,
+    {
+        warn!(
+            "The progress of a nesting bar is given by its children, hence setting it is ignored."
+        );
+    }
+
+    fn start(&self) -> f64 {
+        self.bar.start()
+    }
+
+    fn end(&self) -> f64 {
+        self.bar.end()
+    }
+
+    fn has_progressed_significantly(&self) -> bool {
+        self.bar.has_progressed_significantly()
+            || self
+                .children
+                .iter()
+                .any(|child| child.bar.nested_has_progressed_significantly())
+    }
+
+    fn remember_significant_progress(&mut self) {
+        self.bar.remember_significant_progress();
+        for child in &mut self.children {
+            child.bar.nested_remember_significant_progress();
+        }
+    }
+
+    fn state(&self) -> State {
+        self.bar.state()
+    }
+
+    fn set_state(&mut self, new_state: State) {
+        self.bar.set_state(new_state)
+    }
+
+    fn set_prefix<S>(&mut self, prefix: S)
+    where
+        S: Into<String>,
+    {
+        self.bar.set_prefix(prefix)
+    }
+
+    fn set_message<S>(&mut self, msg: S)
+    where
+        S: Into<String>,
+    {
+        self.bar.set_message(msg)
+    }
+
+    fn tick(&mut self) {
+        self.bar.tick()
+    }
+
+    fn set_stalled(&mut self, is_stalled: bool) {
+        self.bar.set_stalled(is_stalled)
+    }
+}
+
+impl Surrounding for Bar {
+    /// Writes the aggregated line surrounded by the given text (e.g. the approximated time), followed by the children if the layout is a tree.
+    fn fmt_surrounded<L, T>(&self, f: &mut fmt::Formatter<'_>, lead: L, trail: T) -> fmt::Result
+    where
+        L: Display,
+        T: Display,
+    {
+        let percentage = self.bar.progress() * 100.0;
+        self.bar
+            .fmt_line_with(f, lead, format_args!(" {:>3.0}%{}", percentage, trail))?;
+
+        if self.layout == Layout::Tree {
+            for child in &self.children {
+                f.write_str("\n  ")?;
+                write!(Indenting(f), "{}", child.bar)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for Bar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_surrounded(f, "", "")
+    }
+}
+
+/// Indents every line following a newline, e.g. the lines of nested children.
+struct Indenting<'a, 'b>(&'a mut fmt::Formatter<'b>);
+
+impl fmt::Write for Indenting<'_, '_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for (i, line) in s.split('\n').enumerate() {
+            if i > 0 {
+                self.0.write_str("\n  ")?;
+            }
+            self.0.write_str(line)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,145 @@
+//! Integration of the `rayon`-crate (feature `rayon`), driving bars by parallel iterators.
+//!
+//! Worker-threads only increment an atomic counter, while a single thread sets and prints the bar.
+
+use crate::{printing::Printer, Baring};
+use rayon::iter::{Inspect, ParallelIterator};
+use std::{
+    fmt::Display,
+    io::Write,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Condvar, Mutex, PoisonError,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+/// Time between two prints of a bar driven by a parallel iterator
+pub const PRINT_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Counts the items of a parallel iterator and stops the printing thread when dropped,
+/// meaning when the parallel iterator is done.
+#[derive(Debug)]
+struct Counter {
+    count: Arc<AtomicUsize>,
+    /// Whether the printing thread should stop, notified on change
+    is_stopping: Arc<(Mutex<bool>, Condvar)>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Counter {
+    fn spawn<B, W>(mut bar: B, printer: Arc<Mutex<Printer<W>>>) -> Counter
+    where
+        B: Baring<Progress = usize> + Display + Send + 'static,
+        W: Write + Send + 'static,
+    {
+        let count = Arc::new(AtomicUsize::new(0));
+        let is_stopping = Arc::new((Mutex::new(false), Condvar::new()));
+
+        let handle = {
+            let count = Arc::clone(&count);
+            let is_stopping = Arc::clone(&is_stopping);
+            thread::spawn(move || {
+                let start = bar.progress();
+                let print = |bar: &mut B| {
+                    bar.set(start + count.load(Ordering::Relaxed));
+                    {
+                        let mut printer = printer.lock().unwrap_or_else(PoisonError::into_inner);
+                        // Printing must not panic, hence errors are dropped.
+                        let _ = printer.print(bar);
+                    }
+                    // may log a warning, hence after unlocking the printer
+                    bar.take_stall();
+                };
+
+                let (is_stopped, cvar) = &*is_stopping;
+                let mut is_stopped = is_stopped.lock().unwrap_or_else(PoisonError::into_inner);
+                while !*is_stopped {
+                    print(&mut bar);
+                    is_stopped = cvar
+                        .wait_timeout(is_stopped, PRINT_INTERVAL)
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .0;
+                }
+
+                bar.set(start + count.load(Ordering::Relaxed));
+                if !bar.is_done() {
+                    bar.finish();
+                }
+                print(&mut bar);
+            })
+        };
+
+        Counter {
+            count,
+            is_stopping,
+            handle: Some(handle),
+        }
+    }
+
+    fn inc(&self) {
+        self.count.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+impl Drop for Counter {
+    fn drop(&mut self) {
+        let (is_stopping, cvar) = &*self.is_stopping;
+        *is_stopping.lock().unwrap_or_else(PoisonError::into_inner) = true;
+        cvar.notify_all();
+
+        if let Some(handle) = self.handle.take() {
+            // A panicking printing thread has nothing left to clean up.
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Adds `progress(...)` to all parallel iterators, in the style of `ParallelIterator`'s own adaptors.
+///
+/// Every item adds `1` to the given bar, which is printed by a separate thread every `PRINT_INTERVAL`.
+/// When the parallel iterator is done, the bar is finished and printed a last time.
+///
+///
+/// # Mini-Example
+///
+/// ```
+/// use progressing::{mapping::Bar as MappingBar, parallel::ParallelProgressIterator};
+/// use rayon::prelude::*;
+///
+/// fn main() {
+///     let progress_bar = MappingBar::with_range(0usize, 1_000).timed();
+///     let squares: Vec<usize> = (0..1_000usize)
+///         .into_par_iter()
+///         .progress(progress_bar)
+///         .map(|i| i * i)
+///         .collect();
+///     assert_eq!(squares[42], 42 * 42);
+/// }
+/// ```
+pub trait ParallelProgressIterator: ParallelIterator {
+    /// Drives the given bar, which is printed to `stderr`.
+    fn progress<B>(self, bar: B) -> Inspect<Self, impl Fn(&Self::Item) + Sync + Send>
+    where
+        B: Baring<Progress = usize> + Display + Send + 'static,
+    {
+        self.progress_with(bar, Printer::stderr().shared())
+    }
+
+    /// Drives the given bar, which is printed via the given printer.
+    fn progress_with<B, W>(
+        self,
+        bar: B,
+        printer: Arc<Mutex<Printer<W>>>,
+    ) -> Inspect<Self, impl Fn(&Self::Item) + Sync + Send>
+    where
+        B: Baring<Progress = usize> + Display + Send + 'static,
+        W: Write + Send + 'static,
+    {
+        let counter = Counter::spawn(bar, printer);
+        self.inspect(move |_| counter.inc())
+    }
+}
+
+impl<I> ParallelProgressIterator for I where I: ParallelIterator {}
@@ -0,0 +1,500 @@
+//! Printing bars to a terminal or to logs.
+//!
+//! In a terminal, a bar is redrawn in place (using `\r`).
+//! If the output is not a terminal (e.g. in CI or when redirected to a file), one plain line is printed per significant progress, starting with a timestamp.
+//! The environment-variable `PROGRESSING_MODE` (`interactive` or `lines`) forces either mode.
+
+use crate::{terminal, Baring};
+use std::{
+    cell::Cell,
+    env,
+    fmt::{self, Display},
+    io::{self, IsTerminal, Write},
+    mem,
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+use unicode_width::UnicodeWidthChar;
+
+/// How a `Printer` prints bars.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Mode {
+    /// Redraws the bar in place (using `\r`).
+    Interactive,
+    /// Prints one line per significant progress, starting with a timestamp.
+    Lines,
+}
+
+impl Mode {
+    /// Name of the environment-variable forcing a mode (`interactive` or `lines`)
+    pub const ENV_VAR: &'static str = "PROGRESSING_MODE";
+
+    /// Returns the mode forced by the environment-variable, or the mode fitting the given output otherwise.
+    pub fn detect(is_terminal: bool) -> Mode {
+        if let Some(mode) = Mode::from_env() {
+            return mode;
+        }
+
+        let is_dumb = env::var("TERM").is_ok_and(|term| term == "dumb");
+        if is_terminal && !is_dumb {
+            Mode::Interactive
+        } else {
+            Mode::Lines
+        }
+    }
+
+    fn from_env() -> Option<Mode> {
+        match env::var(Mode::ENV_VAR).ok()?.trim().to_lowercase().as_ref() {
+            "interactive" => Some(Mode::Interactive),
+            "lines" => Some(Mode::Lines),
+            _ => None,
+        }
+    }
+}
+
+/// Prints bars to the given output, e.g. `stderr`, according to its `Mode`.
+///
+///
+/// # Mini-Example
+///
+/// ```
+/// use progressing::{mapping::Bar as MappingBar, printing::Printer, Baring};
+///
+/// fn main() -> std::io::Result<()> {
+///     let mut printer = Printer::stderr();
+///     let mut progress_bar = MappingBar::with_range(0, 100);
+///     progress_bar.set_len(20);
+///
+///     for value in 0..=100 {
+///         progress_bar.set(value);
+///         printer.print(&mut progress_bar)?;
+///     }
+///     progress_bar.finish();
+///     printer.print(&mut progress_bar)
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Printer<W>
+where
+    W: Write,
+{
+    out: W,
+    mode: Mode,
+    /// The latest printed frame, used for redrawing and for skipping duplicates
+    last_frame: Option<String>,
+    /// Reused for rendering frames, such that printing doesn't allocate per frame
+    frame_buffer: String,
+    /// Whether the current line is occupied by a bar, which may be redrawn
+    is_line_open: bool,
+    /// Whether bars are rendered with colors, unless set by the bars themselves
+    is_colored: bool,
+}
+
+impl Printer<io::Stderr> {
+    pub fn stderr() -> Printer<io::Stderr> {
+        let is_terminal = io::stderr().is_terminal();
+        let mut printer = Printer::with(io::stderr(), Mode::detect(is_terminal));
+        printer.set_colored(terminal::supports_color_on(is_terminal));
+        printer
+    }
+}
+
+impl Printer<io::Stdout> {
+    pub fn stdout() -> Printer<io::Stdout> {
+        let is_terminal = io::stdout().is_terminal();
+        let mut printer = Printer::with(io::stdout(), Mode::detect(is_terminal));
+        printer.set_colored(terminal::supports_color_on(is_terminal));
+        printer
+    }
+}
+
+impl<W> Printer<W>
+where
+    W: Write,
+{
+    /// Colors are disabled, unless forced (see `terminal::is_color_forced()` and `set_colored(...)`).
+    pub fn with(out: W, mode: Mode) -> Printer<W> {
+        Printer {
+            out,
+            mode,
+            last_frame: None,
+            frame_buffer: String::new(),
+            is_line_open: false,
+            is_colored: terminal::is_color_forced(),
+        }
+    }
+
+    /// Enables or disables colors of bars, which haven't set them themselves (see `clamping::Bar::set_colored(...)`).
+    /// In mode `Lines`, colors are removed anyway.
+    ///
+    ///
+    /// # Mini-Example
+    ///
+    /// ```
+    /// use progressing::{
+    ///     clamping::{Bar as ClampingBar, Effect},
+    ///     printing::{Mode, Printer},
+    ///     Baring,
+    /// };
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let mut progress_bar = ClampingBar::new();
+    ///     progress_bar.set(0.5);
+    ///     progress_bar.set_effect(Effect::Gradient);
+    ///
+    ///     let mut printer = Printer::with(Vec::new(), Mode::Interactive);
+    ///     printer.set_colored(true);
+    ///     printer.print(&mut progress_bar)?;
+    ///     assert!(printer.into_inner().contains(&b'\x1b'));
+    ///
+    ///     // displayed without a printer, the bar stays plain
+    ///     assert!(!progress_bar.to_string().contains('\x1b'));
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_colored(&mut self, is_colored: bool) {
+        self.is_colored = is_colored;
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// Prints the bar, if needed.
+    ///
+    /// Only significant progress (see `has_progressed_significantly()`) and the final frame are printed.
+    /// Interactively, this includes changed prefixes or messages, resizes and ticks of animated effects (see `Baring::tick()`), which are redrawn in place.
+    /// A bar being done leads to a newline after its final frame.
+    ///
+    /// Stalled bars are shown as stalled, but warnings about stalls need `Baring::take_stall()` after printing
+    /// (e.g. by a `ticking::Ticker`), since logging is printed by this printer as well.
+    pub fn print<B>(&mut self, bar: &mut B) -> io::Result<()>
+    where
+        B: Baring + Display,
+    {
+        let _printing = PrintingGuard::enter();
+        let is_significant = {
+            // animated effects are significant, if they are shown
+            let _coloring = ColoringGuard::enter(self.is_coloring());
+            bar.has_progressed_significantly()
+        };
+        if is_significant {
+            bar.remember_significant_progress();
+        }
+
+        let frame = self.render(&*bar);
+        let is_shown = self.mode == Mode::Lines || self.is_line_open || bar.is_done();
+        if is_shown && self.last_frame.as_ref() == Some(&frame) {
+            self.frame_buffer = frame;
+            return Ok(());
+        }
+
+        match self.mode {
+            Mode::Interactive => {
+                // the first frame is drawn anyway
+                if !is_significant && self.is_line_open && !bar.is_done() {
+                    self.frame_buffer = frame;
+                    return Ok(());
+                }
+                self.redraw(&frame)?;
+                if bar.is_done() {
+                    writeln!(self.out)?;
+                    self.is_line_open = false;
+                }
+            }
+            Mode::Lines => {
+                if !is_significant && !bar.is_done() {
+                    self.frame_buffer = frame;
+                    return Ok(());
+                }
+                writeln!(self.out, "{} {}", timestamp(), frame)?;
+            }
+        }
+        self.remember(frame);
+        self.out.flush()
+    }
+
+    /// Draws the given (possibly multi-line) text in place of the currently drawn one, e.g. for several bars at once.
+    /// In mode `Lines`, nothing is printed.
+    pub fn draw<T>(&mut self, frame: T) -> io::Result<()>
+    where
+        T: Display,
+    {
+        let _printing = PrintingGuard::enter();
+        if self.mode == Mode::Interactive {
+            let frame = self.render(frame);
+            if !self.is_line_open || self.last_frame.as_ref() != Some(&frame) {
+                self.redraw(&frame)?;
+                self.remember(frame);
+            } else {
+                self.frame_buffer = frame;
+            }
+        }
+        self.out.flush()
+    }
+
+    /// Prints the given text above the currently drawn bar, which is redrawn afterwards.
+    pub fn println<T>(&mut self, text: T) -> io::Result<()>
+    where
+        T: Display,
+    {
+        let _printing = PrintingGuard::enter();
+        match self.mode {
+            Mode::Interactive => self.suspend(|out| writeln!(out, "{}", text))?,
+            Mode::Lines => {
+                let text = self.render(text);
+                let result = writeln!(self.out, "{} {}", timestamp(), text);
+                self.frame_buffer = text;
+                result
+            }
+        }?;
+        self.out.flush()
+    }
+
+    /// Removes the currently drawn bar, calls `f` and redraws the bar afterwards.
+    /// This allows printing other text (e.g. log-messages) without interfering with the bar.
+    pub fn suspend<F, R>(&mut self, f: F) -> io::Result<R>
+    where
+        F: FnOnce(&mut W) -> R,
+    {
+        let _printing = PrintingGuard::enter();
+        let is_line_open = self.is_line_open;
+        self.clear()?;
+
+        let result = f(&mut self.out);
+
+        if is_line_open {
+            if let Some(frame) = self.last_frame.clone() {
+                self.redraw(&frame)?;
+            }
+        }
+        self.out.flush()?;
+        Ok(result)
+    }
+
+    /// Wraps the printer for being shared, e.g. with a `logging::Logger`.
+    pub fn shared(self) -> Arc<Mutex<Printer<W>>> {
+        Arc::new(Mutex::new(self))
+    }
+
+    /// Removes the currently drawn bar from the terminal.
+    /// In mode `Lines`, nothing is printed.
+    pub fn clear(&mut self) -> io::Result<()> {
+        if self.mode == Mode::Interactive && self.is_line_open {
+            match &self.last_frame {
+                // multiple lines need ANSI-codes for moving the cursor up
+                Some(frame) if frame.contains('\n') => {
+                    write!(self.out, "\r")?;
+                    for _ in 1..frame.lines().count() {
+                        write!(self.out, "\x1b[2K\x1b[1A")?;
+                    }
+                    write!(self.out, "\x1b[2K")?
+                }
+                Some(frame) => write!(self.out, "\r{:1$}\r", "", visible_len(frame))?,
+                None => (),
+            }
+            self.is_line_open = false;
+        }
+        self.out.flush()
+    }
+
+    /// Hides or shows the terminal's cursor, e.g. while bars are redrawn steadily.
+    /// In mode `Lines`, nothing is printed.
+    pub fn set_cursor_visible(&mut self, is_visible: bool) -> io::Result<()> {
+        if self.mode == Mode::Interactive {
+            write!(
+                self.out,
+                "{}",
+                if is_visible { "\x1b[?25h" } else { "\x1b[?25l" }
+            )?;
+        }
+        self.out.flush()
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+
+    /// Renders the given frame into the reused buffer, which is taken until given back or remembered.
+    /// In mode `Lines`, ANSI escape codes (e.g. colors) are removed.
+    fn render<T>(&mut self, frame: T) -> String
+    where
+        T: Display,
+    {
+        let mut buffer = mem::take(&mut self.frame_buffer);
+        buffer.clear();
+        {
+            let _coloring = ColoringGuard::enter(self.is_coloring());
+            // Writing into a String only fails if the frame's formatting fails.
+            let _ = fmt::Write::write_fmt(&mut buffer, format_args!("{}", frame));
+        }
+        // logs stay plain text
+        if self.mode == Mode::Lines {
+            strip_escape_codes(&mut buffer);
+        }
+        buffer
+    }
+
+    /// Returns whether bars are rendered with colors, unless set by the bars themselves.
+    fn is_coloring(&self) -> bool {
+        self.is_colored && self.mode == Mode::Interactive
+    }
+
+    /// Remembers the given frame as the latest frame, whose previous one becomes the buffer.
+    fn remember(&mut self, frame: String) {
+        if let Some(last_frame) = self.last_frame.replace(frame) {
+            self.frame_buffer = last_frame;
+        }
+    }
+
+    /// Overwrites the current line with the given frame.
+    /// Remaining chars of a longer previous frame are overwritten by spaces.
+    fn redraw(&mut self, frame: &str) -> io::Result<()> {
+        let is_multi_line = |frame: &str| frame.contains('\n');
+        if is_multi_line(frame) || self.last_frame.as_deref().is_some_and(is_multi_line) {
+            self.clear()?;
+        }
+
+        let len = visible_len(frame);
+        let last_len = match (&self.last_frame, self.is_line_open) {
+            (Some(last_frame), true) => visible_len(last_frame),
+            _ => 0,
+        };
+        write!(
+            self.out,
+            "\r{}{:2$}",
+            frame,
+            "",
+            last_len.saturating_sub(len)
+        )?;
+        self.is_line_open = true;
+        Ok(())
+    }
+}
+
+thread_local! {
+    /// Number of printers printing on this thread, e.g. while rendering a bar
+    static PRINTING_DEPTH: Cell<usize> = const { Cell::new(0) };
+    static IS_COLORING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Marks the current thread as printing until dropped.
+struct PrintingGuard;
+
+impl PrintingGuard {
+    fn enter() -> PrintingGuard {
+        PRINTING_DEPTH.with(|depth| depth.set(depth.get() + 1));
+        PrintingGuard
+    }
+}
+
+impl Drop for PrintingGuard {
+    fn drop(&mut self) {
+        PRINTING_DEPTH.with(|depth| depth.set(depth.get() - 1));
+    }
+}
+
+/// Marks the current thread as rendering with or without colors until dropped.
+struct ColoringGuard {
+    was_coloring: bool,
+}
+
+impl ColoringGuard {
+    fn enter(is_coloring: bool) -> ColoringGuard {
+        ColoringGuard {
+            was_coloring: IS_COLORING.with(|coloring| coloring.replace(is_coloring)),
+        }
+    }
+}
+
+impl Drop for ColoringGuard {
+    fn drop(&mut self) {
+        IS_COLORING.with(|coloring| coloring.set(self.was_coloring));
+    }
+}
+
+/// Returns whether a printer is rendering with colors on the current thread.
+pub(crate) fn is_coloring() -> bool {
+    IS_COLORING.with(Cell::get)
+}
+
+/// Returns whether a printer is printing on the current thread, whose lock (if shared) is held hence.
+pub(crate) fn is_printing() -> bool {
+    PRINTING_DEPTH.with(Cell::get) > 0
+}
+
+/// Returns the number of columns shown by the terminal (e.g. `2` per emoji), skipping ANSI escape codes (e.g. colors).
+fn visible_len(frame: &str) -> usize {
+    let mut len = 0;
+    let mut chars = frame.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // sequences like `\x1b[0m` end with a letter
+            if chars.next() == Some('[') {
+                chars.find(char::is_ascii_alphabetic);
+            }
+        } else {
+            len += c.width().unwrap_or(0);
+        }
+    }
+    len
+}
+
+/// Removes ANSI escape codes (e.g. colors) from the given frame.
+fn strip_escape_codes(frame: &mut String) {
+    enum Part {
+        Text,
+        Escape,
+        Sequence,
+    }
+
+    let mut part = Part::Text;
+    frame.retain(|c| match part {
+        Part::Text if c == '\x1b' => {
+            part = Part::Escape;
+            false
+        }
+        Part::Text => true,
+        Part::Escape => {
+            // sequences like `\x1b[0m` end with a letter
+            part = if c == '[' { Part::Sequence } else { Part::Text };
+            false
+        }
+        Part::Sequence => {
+            if c.is_ascii_alphabetic() {
+                part = Part::Text;
+            }
+            false
+        }
+    });
+}
+
+/// Returns the current time in UTC, e.g. `2020-04-13T09:41:23Z`.
+fn timestamp() -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+    let (days, secs_of_day) = (secs / 86_400, secs % 86_400);
+
+    // civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        secs_of_day / 3_600,
+        (secs_of_day % 3_600) / 60,
+        secs_of_day % 60
+    )
+}
@@ -0,0 +1,387 @@
+//! Snapshots of a bar's state, e.g. for persisting it (using feature `serde`) or for resuming long-running jobs.
+
+use crate::{bernoulli, clamping, mapping, nesting, timing, Baring, State};
+use log::warn;
+use std::{fmt::Display, fs, io, path::Path, str::FromStr, time::Duration};
+
+/// The state of a bar at a certain point in time.
+///
+/// With feature `serde`, snapshots can be serialized and deserialized.
+///
+///
+/// # Mini-Example
+///
+/// ```
+/// use progressing::{bernoulli::Bar as BernoulliBar, snapshot::Snapshotting, Baring};
+///
+/// fn main() {
+///     let mut progress_bar = BernoulliBar::with_goal(60).timed();
+///     progress_bar.set((42, 130));
+///
+///     let snapshot = progress_bar.snapshot();
+///     assert_eq!(snapshot.progress, 42);
+///     assert_eq!(snapshot.end, 60);
+///     assert_eq!(snapshot.attempts, Some(130));
+///     assert!(snapshot.elapsed.is_some());
+///     assert_eq!(snapshot.sample_interval, Some(std::time::Duration::from_secs(1)));
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Snapshot<N> {
+    pub start: N,
+    pub end: N,
+    pub progress: N,
+    /// Only counted by bars counting attempts, e.g. `bernoulli::Bar`
+    pub attempts: Option<usize>,
+    /// Only measured by timed bars
+    pub elapsed: Option<Duration>,
+    pub state: State,
+    /// Recent rates of progress of timed bars (see `timing::Bar::rates()`), the oldest first
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rates: Vec<f64>,
+    /// Time covered by every rate, only measured by timed bars
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub sample_interval: Option<Duration>,
+}
+
+impl<N> Snapshot<N>
+where
+    N: Display,
+{
+    /// Writes the snapshot as simple `key=value`-lines to the given file.
+    ///
+    /// The file is written next to the given path first and renamed afterwards, hence a crash doesn't corrupt an existing checkpoint.
+    pub fn save<P>(&self, path: P) -> io::Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let mut tmp_path = path.as_os_str().to_owned();
+        tmp_path.push(".tmp");
+
+        let mut text = format!(
+            "start={}\nend={}\nprogress={}\nstate={:?}\n",
+            self.start, self.end, self.progress, self.state
+        );
+        if let Some(attempts) = self.attempts {
+            text.push_str(&format!("attempts={}\n", attempts));
+        }
+        if let Some(elapsed) = self.elapsed {
+            text.push_str(&format!("elapsed_ms={}\n", elapsed.as_millis()));
+        }
+        if let Some(sample_interval) = self.sample_interval {
+            // nanoseconds, since intervals may be shorter than a millisecond
+            text.push_str(&format!(
+                "sample_interval_ns={}\n",
+                sample_interval.as_nanos()
+            ));
+        }
+        if !self.rates.is_empty() {
+            let rates: Vec<String> = self.rates.iter().map(f64::to_string).collect();
+            text.push_str(&format!("rates={}\n", rates.join(",")));
+        }
+
+        fs::write(&tmp_path, text)?;
+        fs::rename(&tmp_path, path)
+    }
+}
+
+impl<N> Snapshot<N>
+where
+    N: FromStr,
+{
+    /// Reads a snapshot written by `save(...)`.
+    pub fn load<P>(path: P) -> io::Result<Snapshot<N>>
+    where
+        P: AsRef<Path>,
+    {
+        fn invalid(msg: String) -> io::Error {
+            io::Error::new(io::ErrorKind::InvalidData, msg)
+        }
+        fn parse<T>(key: &str, value: Option<&str>) -> io::Result<T>
+        where
+            T: FromStr,
+        {
+            let value = value.ok_or_else(|| invalid(format!("Snapshot misses {}", key)))?;
+            value
+                .parse()
+                .map_err(|_| invalid(format!("Snapshot has invalid {}: {}", key, value)))
+        }
+
+        let text = fs::read_to_string(path)?;
+        let value = |key: &str| {
+            text.lines()
+                .filter_map(|line| line.split_once('='))
+                .find(|(k, _)| k.trim() == key)
+                .map(|(_, v)| v.trim())
+        };
+
+        let state = match value("state") {
+            Some("Running") => State::Running,
+            Some("Finished") => State::Finished,
+            Some("Abandoned") => State::Abandoned,
+            Some("Failed") => State::Failed,
+            other => return Err(invalid(format!("Snapshot has invalid state: {:?}", other))),
+        };
+        let attempts = match value("attempts") {
+            Some(_) => Some(parse("attempts", value("attempts"))?),
+            None => None,
+        };
+        let elapsed = match value("elapsed_ms") {
+            Some(_) => Some(Duration::from_millis(parse(
+                "elapsed_ms",
+                value("elapsed_ms"),
+            )?)),
+            None => None,
+        };
+        // intervals are positive, hence zero is treated like a missing interval
+        let sample_interval = match value("sample_interval_ns") {
+            Some(_) => Some(Duration::from_nanos(parse(
+                "sample_interval_ns",
+                value("sample_interval_ns"),
+            )?)),
+            None => None,
+        }
+        .filter(|interval| !interval.is_zero());
+        let rates = match value("rates") {
+            Some(rates) => rates
+                .split(',')
+                .map(|rate| parse("rates", Some(rate)))
+                .collect::<io::Result<Vec<f64>>>()?,
+            None => Vec::new(),
+        };
+
+        Ok(Snapshot {
+            start: parse("start", value("start"))?,
+            end: parse("end", value("end"))?,
+            progress: parse("progress", value("progress"))?,
+            attempts,
+            elapsed,
+            state,
+            rates,
+            sample_interval,
+        })
+    }
+}
+
+/// Bars providing snapshots of their state, which can be restored later, e.g. when resuming a job.
+///
+///
+/// # Mini-Example
+///
+/// ```
+/// use progressing::{mapping::Bar as MappingBar, snapshot::Snapshotting, Baring};
+/// use std::{thread, time::Duration};
+///
+/// fn main() -> std::io::Result<()> {
+///     let path = std::env::temp_dir().join("progressing-doc-checkpoint.txt");
+///
+///     let mut progress_bar = MappingBar::with_range(0usize, 1_000).timed();
+///     progress_bar.set_sample_interval(Duration::from_millis(1));
+///     thread::sleep(Duration::from_millis(5));
+///     progress_bar.set(420usize);
+///     progress_bar.checkpoint(&path)?;
+///
+///     // e.g. after a restart, including the history of rates
+///     let mut resumed_bar = MappingBar::with_range(0usize, 1_000).timed();
+///     resumed_bar.resume(&path)?;
+///     assert_eq!(resumed_bar.progress(), 420);
+///     assert_eq!(resumed_bar.rates(), progress_bar.rates());
+///     Ok(())
+/// }
+/// ```
+pub trait Snapshotting {
+    type Value;
+
+    fn snapshot(&self) -> Snapshot<Self::Value>;
+
+    /// Returns the progress mapped to `[0, 1]`.
+    fn fraction(&self) -> f64;
+
+    /// Restores progress, attempts, elapsed time, rates and state of the given snapshot.
+    /// The current, incomplete interval of the rates isn't kept.
+    /// The range is defined by the bar itself and hence not restored.
+    fn restore(&mut self, snapshot: &Snapshot<Self::Value>);
+
+    /// Saves a snapshot to the given file (see `Snapshot::save(...)`).
+    fn checkpoint<P>(&self, path: P) -> io::Result<()>
+    where
+        P: AsRef<Path>,
+        Self::Value: Display,
+    {
+        self.snapshot().save(path)
+    }
+
+    /// Restores the snapshot saved in the given file (see `checkpoint(...)`).
+    ///
+    /// Timed bars continue measuring time from the saved elapsed time, such that the approximated remaining time accounts for prior work.
+    fn resume<P>(&mut self, path: P) -> io::Result<()>
+    where
+        P: AsRef<Path>,
+        Self::Value: FromStr,
+    {
+        let snapshot = Snapshot::load(path)?;
+        self.restore(&snapshot);
+        Ok(())
+    }
+}
+
+impl Snapshotting for clamping::Bar {
+    type Value = f64;
+
+    fn snapshot(&self) -> Snapshot<f64> {
+        Snapshot {
+            start: self.start(),
+            end: self.end(),
+            progress: self.progress(),
+            attempts: None,
+            elapsed: None,
+            state: self.state(),
+            rates: Vec::new(),
+            sample_interval: None,
+        }
+    }
+
+    fn fraction(&self) -> f64 {
+        self.progress()
+    }
+
+    fn restore(&mut self, snapshot: &Snapshot<f64>) {
+        self.set_state(State::Running);
+        self.set(snapshot.progress);
+        self.set_state(snapshot.state);
+    }
+}
+
+impl<N> Snapshotting for mapping::Bar<N>
+where
+    N: Clone + PartialEq,
+    mapping::Bar<N>: Baring<Progress = N>,
+{
+    type Value = N;
+
+    fn snapshot(&self) -> Snapshot<N> {
+        Snapshot {
+            start: self.start(),
+            end: self.end(),
+            progress: self.progress(),
+            attempts: None,
+            elapsed: None,
+            state: self.state(),
+            rates: Vec::new(),
+            sample_interval: None,
+        }
+    }
+
+    fn fraction(&self) -> f64 {
+        self.bar.progress()
+    }
+
+    fn restore(&mut self, snapshot: &Snapshot<N>) {
+        if snapshot.start != self.start() || snapshot.end != self.end() {
+            warn!(
+                "The snapshot's range differs from the bar's range, hence the bar's range is kept."
+            );
+        }
+
+        self.set_state(State::Running);
+        self.set(snapshot.progress.clone());
+        self.set_state(snapshot.state);
+    }
+}
+
+impl Snapshotting for bernoulli::Bar {
+    type Value = usize;
+
+    fn snapshot(&self) -> Snapshot<usize> {
+        let progress = self.progress();
+        Snapshot {
+            start: self.start().successes,
+            end: self.end().successes,
+            progress: progress.successes,
+            attempts: Some(progress.attempts),
+            elapsed: None,
+            state: self.state(),
+            rates: Vec::new(),
+            sample_interval: None,
+        }
+    }
+
+    fn fraction(&self) -> f64 {
+        self.bar.bar.progress()
+    }
+
+    fn restore(&mut self, snapshot: &Snapshot<usize>) {
+        if snapshot.end != self.end().successes {
+            warn!("The snapshot's goal differs from the bar's goal, hence the bar's goal is kept.");
+        }
+
+        let attempts = snapshot.attempts.unwrap_or(snapshot.progress);
+        self.set_state(State::Running);
+        self.set((snapshot.progress, attempts));
+        self.set_state(snapshot.state);
+    }
+}
+
+/// The progress of a `nesting::Bar` is given by its children, hence only its state is restored.
+impl Snapshotting for nesting::Bar {
+    type Value = f64;
+
+    fn snapshot(&self) -> Snapshot<f64> {
+        Snapshot {
+            start: self.start(),
+            end: self.end(),
+            progress: self.progress(),
+            attempts: None,
+            elapsed: None,
+            state: self.state(),
+            rates: Vec::new(),
+            sample_interval: None,
+        }
+    }
+
+    fn fraction(&self) -> f64 {
+        self.progress()
+    }
+
+    fn restore(&mut self, snapshot: &Snapshot<f64>) {
+        self.set_state(snapshot.state);
+    }
+}
+
+impl<B> Snapshotting for timing::Bar<B>
+where
+    B: Baring + Snapshotting,
+    B::Value: Clone,
+{
+    type Value = B::Value;
+
+    fn snapshot(&self) -> Snapshot<B::Value> {
+        Snapshot {
+            elapsed: Some(self.elapsed()),
+            rates: self.rates().to_vec(),
+            sample_interval: Some(self.sample_interval()),
+            ..self.inner().snapshot()
+        }
+    }
+
+    fn fraction(&self) -> f64 {
+        self.inner().fraction()
+    }
+
+    fn restore(&mut self, snapshot: &Snapshot<B::Value>) {
+        self.set_state(State::Running);
+        self.inner_mut().restore(&Snapshot {
+            state: State::Running,
+            ..snapshot.clone()
+        });
+        self.set_elapsed(snapshot.elapsed.unwrap_or_default());
+        // a zero interval (e.g. of a hand-made snapshot) would panic
+        if let Some(sample_interval) = snapshot.sample_interval.filter(|i| !i.is_zero()) {
+            self.set_sample_interval(sample_interval);
+        }
+        self.set_rates(&snapshot.rates);
+        self.set_state(snapshot.state);
+    }
+}
@@ -0,0 +1,409 @@
+//! A progress-bar for fixed sequences of named stages, e.g. `[3/7] Compiling`.
+
+use crate::{
+    clamping::{self, fmt_truncated, FmtFn},
+    snapshot::Snapshotting,
+    timing::{self, fmt_time},
+    Baring, State, Surrounding,
+};
+use log::warn;
+use std::{
+    fmt::{self, Display},
+    time::Duration,
+};
+
+/// A progress-bar running through named stages, showing the current stage and its progress.
+///
+/// Every stage has its own bar, which is passed when entering the stage.
+/// The remaining time of the whole pipeline is approximated from the durations of the stages done so far.
+///
+///
+/// # Mini-Example
+///
+/// ```
+/// use progressing::{mapping::Bar as MappingBar, staging::Bar as StagingBar, Baring};
+///
+/// /// Pipeline of three stages, currently compiling
+/// /// [2/3] Compiling [=====>............] (5/16) ~2s
+/// fn main() {
+///     let stages = vec!["Fetching", "Compiling", "Linking"];
+///     let mut pipeline = StagingBar::with_stages(stages, MappingBar::with_range(0, 60));
+///     pipeline.set_len(20);
+///     pipeline.set(60);
+///
+///     pipeline.next_stage(MappingBar::with_range(0, 16));
+///     pipeline.set(5);
+///     println!("{}", pipeline);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Bar<B>
+where
+    B: Baring,
+{
+    stages: Vec<String>,
+    /// The bar of the current stage
+    bar: B,
+    /// Index of the current stage
+    current: usize,
+    prefix: Option<String>,
+    max_prefix_len: usize,
+    /// Written in front of the current stage's bar, e.g. `[2/3] Compiling `
+    label: String,
+    bar_len: Option<usize>,
+    min_bar_len: Option<usize>,
+    /// Durations of the stages done so far
+    durations: Vec<Duration>,
+    /// Returns the time since an arbitrary, but fixed point in time
+    clock: fn() -> Duration,
+    /// The clock's time when entering the current stage
+    stage_started: Duration,
+    /// The clock's time when starting
+    started: Duration,
+    /// Frozen as soon as the pipeline is done
+    total_duration: Option<Duration>,
+    state: State,
+    has_changed_stage: bool,
+}
+
+impl<B> Bar<B>
+where
+    B: Baring,
+{
+    /// Creates a pipeline of the given stages, entering the first stage with the given bar.
+    /// Time is measured by `timing::std_clock`.
+    ///
+    /// panics if no stages are given
+    pub fn with_stages<I, S>(stages: I, bar: B) -> Bar<B>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Bar::with_stages_timed_by(stages, bar, timing::std_clock)
+    }
+
+    /// Creates a pipeline like `with_stages(...)`, measuring time by the given clock (see `timing::Bar`).
+    ///
+    /// panics if no stages are given
+    ///
+    ///
+    /// # Mini-Example
+    ///
+    /// ```
+    /// use progressing::{mapping::Bar as MappingBar, staging::Bar as StagingBar, Baring};
+    /// use std::{
+    ///     sync::atomic::{AtomicU64, Ordering},
+    ///     time::Duration,
+    /// };
+    ///
+    /// static NOW_S: AtomicU64 = AtomicU64::new(0);
+    ///
+    /// fn clock() -> Duration {
+    ///     Duration::from_secs(NOW_S.load(Ordering::Relaxed))
+    /// }
+    ///
+    /// fn main() {
+    ///     let stages = vec!["Fetching", "Compiling", "Linking"];
+    ///     let bar = MappingBar::with_range(0, 10);
+    ///     let mut pipeline = StagingBar::with_stages_timed_by(stages, bar, clock);
+    ///     pipeline.set_len(12);
+    ///
+    ///     NOW_S.store(20, Ordering::Relaxed);
+    ///     pipeline.set(10);
+    ///     pipeline.next_stage(MappingBar::with_range(0, 10));
+    ///     assert_eq!(pipeline.durations(), &[Duration::from_secs(20)]);
+    ///
+    ///     // half of the second stage, the last stage is expected to take as long as the first one
+    ///     NOW_S.store(30, Ordering::Relaxed);
+    ///     pipeline.set(5);
+    ///     assert_eq!(
+    ///         pipeline.to_string(),
+    ///         "[2/3] Compiling [=====>....] ( 5/10) ~  30s"
+    ///     );
+    /// }
+    /// ```
+    pub fn with_stages_timed_by<I, S>(stages: I, bar: B, clock: fn() -> Duration) -> Bar<B>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let stages: Vec<String> = stages.into_iter().map(Into::into).collect();
+        assert!(!stages.is_empty(), "A pipeline needs at least one stage.");
+
+        let now = clock();
+        let mut pipeline = Bar {
+            stages,
+            bar,
+            current: 0,
+            prefix: None,
+            max_prefix_len: clamping::Config::new().max_prefix_len,
+            label: String::new(),
+            bar_len: None,
+            min_bar_len: None,
+            durations: Vec::new(),
+            clock,
+            stage_started: now,
+            started: now,
+            total_duration: None,
+            state: State::Running,
+            has_changed_stage: true,
+        };
+        pipeline.update_stage_bar();
+        pipeline
+    }
+
+    /// Finishes the current stage (if still running) and enters the next stage with the given bar.
+    /// Entering a stage after the last one is ignored.
+    pub fn next_stage(&mut self, bar: B) {
+        if self.is_done() {
+            warn!(
+                "The pipeline is already done ({:?}), hence entering a new stage is ignored.",
+                self.state
+            );
+            return;
+        }
+        if self.current + 1 >= self.stages.len() {
+            warn!(
+                "The pipeline is already in its last stage, hence entering a new stage is ignored."
+            );
+            return;
+        }
+
+        if !self.bar.is_done() {
+            self.bar.finish();
+        }
+        let now = (self.clock)();
+        self.durations.push(now.saturating_sub(self.stage_started));
+        self.stage_started = now;
+        self.current += 1;
+        self.bar = bar;
+        self.has_changed_stage = true;
+        self.update_stage_bar();
+    }
+
+    /// Returns the index of the current stage, starting at `0`.
+    pub fn stage(&self) -> usize {
+        self.current
+    }
+
+    pub fn stage_name(&self) -> &str {
+        &self.stages[self.current]
+    }
+
+    pub fn stage_count(&self) -> usize {
+        self.stages.len()
+    }
+
+    /// Returns the durations of the stages done so far.
+    pub fn durations(&self) -> &[Duration] {
+        &self.durations
+    }
+
+    pub fn stage_bar(&self) -> &B {
+        &self.bar
+    }
+
+    /// Gives access to the bar of the current stage, e.g. for setting its message.
+    pub fn stage_bar_mut(&mut self) -> &mut B {
+        &mut self.bar
+    }
+
+    /// Returns the time since creation, or the total duration if the pipeline is done.
+    pub fn elapsed(&self) -> Duration {
+        self.total_duration
+            .unwrap_or_else(|| (self.clock)().saturating_sub(self.started))
+    }
+
+    /// Updates the stage-label and applies the pipeline's length to the bar of the current stage.
+    ///
+    /// The label is padded to the longest stage, such that all stages have the same length.
+    /// It isn't part of the bar's prefix, hence long stage-names aren't truncated.
+    fn update_stage_bar(&mut self) {
+        let count_width = self.stages.len().to_string().len();
+        let name_width = self.stages.iter().map(|stage| stage.chars().count()).max();
+        let label = format!(
+            "[{:>count_width$}/{}] {:<name_width$} ",
+            self.current + 1,
+            self.stages.len(),
+            self.stages[self.current],
+            count_width = count_width,
+            name_width = name_width.unwrap_or(0)
+        );
+        self.label = match &self.prefix {
+            Some(prefix) => {
+                let max_prefix_len = self.max_prefix_len;
+                let prefix = FmtFn(|f| fmt_truncated(f, prefix, max_prefix_len));
+                format!("{} {}", prefix, label)
+            }
+            None => label,
+        };
+        if let Some(bar_len) = self.bar_len {
+            self.bar.set_len(bar_len);
+        }
+        if let Some(min_bar_len) = self.min_bar_len {
+            self.bar.fit_terminal(min_bar_len);
+        }
+    }
+}
+
+impl<B> Bar<B>
+where
+    B: Baring + Snapshotting,
+{
+    /// Approximates the remaining time of the current stage by its own progress,
+    /// and the remaining time of the following stages by the average duration of the stages done so far.
+    fn approx_secs(&self) -> Option<usize> {
+        let stage_elapsed_s = (self.clock)()
+            .saturating_sub(self.stage_started)
+            .as_secs_f64();
+        let fraction = self.bar.fraction();
+        let avg_s = if self.durations.is_empty() {
+            None
+        } else {
+            let total_s: f64 = self.durations.iter().map(Duration::as_secs_f64).sum();
+            Some(total_s / self.durations.len() as f64)
+        };
+
+        // expected duration of the current stage
+        let stage_s = if fraction > 0.0 {
+            Some(stage_elapsed_s / fraction)
+        } else {
+            avg_s
+        };
+        stage_s.map(|stage_s| {
+            let following_stages = self.stages.len() - self.current - 1;
+            let remaining_s = (stage_s - stage_elapsed_s).max(0.0)
+                + avg_s.unwrap_or(stage_s) * following_stages as f64;
+            remaining_s as usize
+        })
+    }
+}
+
+impl<B> Baring for Bar<B>
+where
+    B: Baring,
+{
+    type Progress = B::Progress;
+
+    fn len(&self) -> usize {
+        self.bar.len()
+    }
+
+    /// Applies to the bars of all following stages as well.
+    fn set_len(&mut self, new_bar_len: usize) {
+        self.bar_len = Some(new_bar_len);
+        self.min_bar_len = None;
+        self.bar.set_len(new_bar_len)
+    }
+
+    /// Applies to the bars of all following stages as well.
+    fn fit_terminal(&mut self, min_bar_len: usize) {
+        self.bar_len = None;
+        self.min_bar_len = Some(min_bar_len);
+        self.bar.fit_terminal(min_bar_len)
+    }
+
+    /// Returns the progress of the current stage.
+    fn progress(&self) -> B::Progress {
+        self.bar.progress()
+    }
+
+    /// Sets the progress of the current stage.
+    fn set<P>(&mut self, new_progress: P)
+    where
+        P: Into<B::Progress>,
+    {
+        if self.is_done() {
+            warn!(
+                "The pipeline is already done ({:?}), hence new progress is ignored.",
+                self.state
+            );
+            return;
+        }
+        self.bar.set(new_progress)
+    }
+
+    fn start(&self) -> B::Progress {
+        self.bar.start()
+    }
+
+    fn end(&self) -> B::Progress {
+        self.bar.end()
+    }
+
+    fn has_progressed_significantly(&self) -> bool {
+        self.has_changed_stage || self.bar.has_progressed_significantly()
+    }
+
+    fn remember_significant_progress(&mut self) {
+        self.bar.remember_significant_progress();
+        self.has_changed_stage = false;
+    }
+
+    fn state(&self) -> State {
+        self.state
+    }
+
+    /// Marking the pipeline as done marks the current stage's bar as well, if it is still running.
+    fn set_state(&mut self, new_state: State) {
+        if new_state.is_done() {
+            if self.total_duration.is_none() {
+                self.total_duration = Some(self.elapsed());
+            }
+            if !self.bar.is_done() {
+                self.bar.set_state(new_state);
+            }
+        } else {
+            self.total_duration = None;
+        }
+        self.state = new_state;
+    }
+
+    /// Like the prefixes of other bars, it is truncated with an ellipsis if too long.
+    fn set_prefix<S>(&mut self, prefix: S)
+    where
+        S: Into<String>,
+    {
+        let prefix = Some(prefix.into()).filter(|prefix| !prefix.is_empty());
+        if prefix != self.prefix {
+            self.prefix = prefix;
+            self.has_changed_stage = true;
+            self.update_stage_bar();
+        }
+    }
+
+    /// Sets the message of the current stage's bar.
+    fn set_message<S>(&mut self, msg: S)
+    where
+        S: Into<String>,
+    {
+        self.bar.set_message(msg)
+    }
+
+    fn tick(&mut self) {
+        self.bar.tick()
+    }
+
+    fn set_stalled(&mut self, is_stalled: bool) {
+        self.bar.set_stalled(is_stalled)
+    }
+
+    /// Takes the stall of the current stage's bar, if any.
+    fn take_stall(&mut self) -> Option<Duration> {
+        self.bar.take_stall()
+    }
+}
+
+/// The stage-label and the approximated time surround the current stage's bar, such that the whole line fits the terminal.
+impl<B> Display for Bar<B>
+where
+    B: Baring + Snapshotting + Surrounding,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.state;
+        let total_secs = self.elapsed().as_secs() as usize;
+        let approx_secs = self.approx_secs();
+        let time = FmtFn(move |f| fmt_time(f, state, total_secs, approx_secs));
+        self.bar.fmt_surrounded(f, &self.label, time)
+    }
+}
@@ -0,0 +1,206 @@
+//! Helpers querying the terminal, e.g. its width.
+//!
+//! Without feature `std`, the terminal is unknown.
+
+// Resizes are only tracked by bars needing `alloc`.
+#![cfg_attr(not(feature = "alloc"), allow(dead_code))]
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(feature = "std")]
+use std::env;
+
+/// Is increased whenever the terminal is resized (`SIGWINCH` on Unix).
+static RESIZE_GENERATION: AtomicUsize = AtomicUsize::new(0);
+
+/// Caches the environment-variable `COLUMNS` (`0` if unset or invalid), since bars ask for the width on every render.
+#[cfg(feature = "std")]
+static COLUMNS: AtomicUsize = AtomicUsize::new(UNREAD_COLUMNS);
+#[cfg(feature = "std")]
+const UNREAD_COLUMNS: usize = usize::MAX;
+
+/// Returns the number of columns of the terminal, if known.
+///
+/// The terminal itself is asked first, such that resizes are noticed.
+/// If no terminal answers (e.g. when redirected), the environment-variable `COLUMNS` is used,
+/// which is read only once, hence changing it later has no effect.
+#[cfg(feature = "std")]
+pub fn width() -> Option<usize> {
+    if let Some(columns) = queried_width() {
+        return Some(columns);
+    }
+
+    let mut columns = COLUMNS.load(Ordering::Relaxed);
+    if columns == UNREAD_COLUMNS {
+        columns = env::var("COLUMNS")
+            .ok()
+            .and_then(|columns| columns.trim().parse::<usize>().ok())
+            .filter(|&columns| columns < UNREAD_COLUMNS)
+            .unwrap_or(0);
+        COLUMNS.store(columns, Ordering::Relaxed);
+    }
+
+    if columns > 0 {
+        Some(columns)
+    } else {
+        None
+    }
+}
+
+#[cfg(not(feature = "std"))]
+pub fn width() -> Option<usize> {
+    None
+}
+
+#[cfg(all(unix, feature = "std"))]
+fn queried_width() -> Option<usize> {
+    // stderr first, since stdout is more likely to be redirected
+    for fd in &[libc::STDERR_FILENO, libc::STDOUT_FILENO, libc::STDIN_FILENO] {
+        let mut size: libc::winsize = unsafe { core::mem::zeroed() };
+        let result = unsafe { libc::ioctl(*fd, libc::TIOCGWINSZ, &mut size) };
+        if result == 0 && size.ws_col > 0 {
+            return Some(size.ws_col as usize);
+        }
+    }
+    None
+}
+
+#[cfg(all(not(unix), feature = "std"))]
+fn queried_width() -> Option<usize> {
+    None
+}
+
+/// Returns whether the terminal supports UTF-8, according to the locale (the first of `LC_ALL`, `LC_CTYPE` and `LANG` being set).
+/// Without any locale, UTF-8 is assumed on Windows only.
+#[cfg(feature = "std")]
+pub fn supports_utf8() -> bool {
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|name| env::var(name).ok())
+        .find(|locale| !locale.trim().is_empty());
+
+    match locale {
+        Some(locale) => {
+            let locale = locale.to_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        }
+        None => cfg!(windows),
+    }
+}
+
+#[cfg(not(feature = "std"))]
+pub fn supports_utf8() -> bool {
+    false
+}
+
+/// Returns whether colors (ANSI escape codes) should be written to `stderr` (see `supports_color_on(...)`).
+#[cfg(feature = "std")]
+pub fn supports_color() -> bool {
+    supports_color_on(std::io::IsTerminal::is_terminal(&std::io::stderr()))
+}
+
+#[cfg(not(feature = "std"))]
+pub fn supports_color() -> bool {
+    false
+}
+
+/// Returns whether colors (ANSI escape codes) should be written to an output, which is a terminal or not.
+///
+/// Colors are disabled by a non-empty `NO_COLOR`, forced by a non-empty `CLICOLOR_FORCE` (other than `0`),
+/// and otherwise enabled for terminals, which are not `TERM=dumb`.
+#[cfg(feature = "std")]
+pub fn supports_color_on(is_terminal: bool) -> bool {
+    if is_color_disabled() {
+        return false;
+    }
+    if is_color_forced() {
+        return true;
+    }
+
+    let is_dumb = env::var("TERM").is_ok_and(|term| term == "dumb");
+    !is_dumb && is_terminal
+}
+
+#[cfg(not(feature = "std"))]
+pub fn supports_color_on(_is_terminal: bool) -> bool {
+    false
+}
+
+/// Returns whether colors are forced by a non-empty `CLICOLOR_FORCE` (other than `0`) and not disabled by `NO_COLOR`,
+/// such that they are written to any output, e.g. a file.
+#[cfg(feature = "std")]
+pub fn is_color_forced() -> bool {
+    !is_color_disabled()
+        && env::var_os("CLICOLOR_FORCE").is_some_and(|value| !value.is_empty() && value != "0")
+}
+
+#[cfg(not(feature = "std"))]
+pub fn is_color_forced() -> bool {
+    false
+}
+
+#[cfg(feature = "std")]
+fn is_color_disabled() -> bool {
+    env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
+/// Returns a number, which changes whenever the terminal has been resized.
+///
+/// On Unix, the first call installs a handler for `SIGWINCH`, which calls the previously installed handler (if any) as well.
+/// On other platforms (or without feature `std`), the number never changes.
+pub(crate) fn resize_generation() -> usize {
+    listen_to_resizes();
+    RESIZE_GENERATION.load(Ordering::Relaxed)
+}
+
+#[cfg(all(unix, feature = "std"))]
+fn listen_to_resizes() {
+    use core::{ffi::c_void, mem};
+    use std::sync::{
+        atomic::{AtomicBool, AtomicUsize},
+        Once,
+    };
+
+    static INSTALL_HANDLER: Once = Once::new();
+    /// The handler installed before, e.g. by the host application
+    static PREVIOUS_HANDLER: AtomicUsize = AtomicUsize::new(libc::SIG_DFL);
+    static IS_PREVIOUS_SIGINFO: AtomicBool = AtomicBool::new(false);
+
+    extern "C" fn on_resize(signal: libc::c_int, info: *mut libc::siginfo_t, context: *mut c_void) {
+        RESIZE_GENERATION.fetch_add(1, Ordering::Relaxed);
+
+        let previous = PREVIOUS_HANDLER.load(Ordering::Relaxed);
+        if previous == libc::SIG_DFL || previous == libc::SIG_IGN {
+            return;
+        }
+        unsafe {
+            if IS_PREVIOUS_SIGINFO.load(Ordering::Relaxed) {
+                let previous: extern "C" fn(libc::c_int, *mut libc::siginfo_t, *mut c_void) =
+                    mem::transmute(previous);
+                previous(signal, info, context);
+            } else {
+                let previous: extern "C" fn(libc::c_int) = mem::transmute(previous);
+                previous(signal);
+            }
+        }
+    }
+
+    INSTALL_HANDLER.call_once(|| unsafe {
+        let mut previous: libc::sigaction = mem::zeroed();
+        if libc::sigaction(libc::SIGWINCH, core::ptr::null(), &mut previous) != 0 {
+            return;
+        }
+        PREVIOUS_HANDLER.store(previous.sa_sigaction, Ordering::Relaxed);
+        IS_PREVIOUS_SIGINFO.store(previous.sa_flags & libc::SA_SIGINFO != 0, Ordering::Relaxed);
+
+        let mut action: libc::sigaction = mem::zeroed();
+        action.sa_sigaction = on_resize
+            as extern "C" fn(libc::c_int, *mut libc::siginfo_t, *mut c_void)
+            as libc::sighandler_t;
+        action.sa_flags = libc::SA_SIGINFO | libc::SA_RESTART;
+        libc::sigemptyset(&mut action.sa_mask);
+        libc::sigaction(libc::SIGWINCH, &action, core::ptr::null_mut());
+    });
+}
+
+#[cfg(not(all(unix, feature = "std")))]
+fn listen_to_resizes() {}
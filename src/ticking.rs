@@ -0,0 +1,146 @@
+//! Redrawing a shared bar steadily from a background thread.
+//!
+//! Without a ticker, bars are only redrawn when progress is set, hence a slow step freezes the display, including the approximated time.
+
+use crate::{printing::Printer, Baring};
+use std::{
+    fmt::Display,
+    io::Write,
+    sync::{Arc, Condvar, Mutex, PoisonError},
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+/// A background thread printing a shared bar via a shared `Printer` at a fixed interval.
+///
+/// Every tick moves the bar's animated effects to their next frame (see `Baring::tick()`) and prints the bar as `Printer::print(...)` does, hence the printer's mode decides whether the frame is redrawn.
+/// The ticker stops as soon as the bar is done, when calling `stop()`, or when it is dropped.
+/// While ticking interactively, the cursor is hidden and shown again when stopping.
+///
+/// Lock the bar before the printer (as the ticker does), to not deadlock with the ticker.
+///
+///
+/// # Mini-Example
+///
+/// ```
+/// use progressing::{mapping::Bar as MappingBar, printing::Printer, ticking::Ticker, Baring};
+/// use std::{
+///     sync::{Arc, Mutex},
+///     thread,
+///     time::Duration,
+/// };
+///
+/// fn main() {
+///     let progress_bar = Arc::new(Mutex::new(MappingBar::with_range(0, 10).timed()));
+///     let ticker = Ticker::spawn(
+///         Arc::clone(&progress_bar),
+///         Printer::stderr().shared(),
+///         Duration::from_millis(10),
+///     );
+///
+///     for value in 0..=10 {
+///         // a slow step, while the ticker keeps the approximated time up to date
+///         thread::sleep(Duration::from_millis(20));
+///         progress_bar.lock().unwrap().set(value);
+///     }
+///     progress_bar.lock().unwrap().finish();
+///     ticker.stop();
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Ticker {
+    /// Whether the ticker should stop, notified on change
+    is_stopping: Arc<(Mutex<bool>, Condvar)>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Ticker {
+    /// Starts a thread printing the given bar every `interval`.
+    pub fn spawn<B, W>(
+        bar: Arc<Mutex<B>>,
+        printer: Arc<Mutex<Printer<W>>>,
+        interval: Duration,
+    ) -> Ticker
+    where
+        B: Baring + Display + Send + 'static,
+        W: Write + Send + 'static,
+    {
+        let is_stopping = Arc::new((Mutex::new(false), Condvar::new()));
+        let handle = {
+            let is_stopping = Arc::clone(&is_stopping);
+            thread::spawn(move || tick(&bar, &printer, interval, &is_stopping))
+        };
+
+        Ticker {
+            is_stopping,
+            handle: Some(handle),
+        }
+    }
+
+    /// Stops the ticker after printing the bar a last time, and waits for its thread.
+    pub fn stop(mut self) {
+        self.join();
+    }
+
+    fn join(&mut self) {
+        let (is_stopping, cvar) = &*self.is_stopping;
+        *is_stopping.lock().unwrap_or_else(PoisonError::into_inner) = true;
+        cvar.notify_all();
+
+        if let Some(handle) = self.handle.take() {
+            // A panicking ticker has nothing left to clean up.
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for Ticker {
+    fn drop(&mut self) {
+        self.join();
+    }
+}
+
+fn tick<B, W>(
+    bar: &Mutex<B>,
+    printer: &Mutex<Printer<W>>,
+    interval: Duration,
+    is_stopping: &(Mutex<bool>, Condvar),
+) where
+    B: Baring + Display,
+    W: Write,
+{
+    let print = || {
+        let mut bar = bar.lock().unwrap_or_else(PoisonError::into_inner);
+        // animated effects move on with every tick
+        bar.tick();
+        {
+            let mut printer = printer.lock().unwrap_or_else(PoisonError::into_inner);
+            // Ticking must not panic, hence errors are dropped.
+            let _ = printer.print(&mut *bar);
+        }
+        // may log a warning, hence after unlocking the printer
+        bar.take_stall();
+        bar.is_done()
+    };
+    let set_cursor_visible = |is_visible| {
+        let mut printer = printer.lock().unwrap_or_else(PoisonError::into_inner);
+        let _ = printer.set_cursor_visible(is_visible);
+    };
+
+    set_cursor_visible(false);
+    let (is_stopping, cvar) = is_stopping;
+    while !print() {
+        let is_stopped = is_stopping.lock().unwrap_or_else(PoisonError::into_inner);
+        if *is_stopped {
+            break;
+        }
+        let (is_stopped, _) = cvar
+            .wait_timeout(is_stopped, interval)
+            .unwrap_or_else(PoisonError::into_inner);
+        if *is_stopped {
+            break;
+        }
+    }
+    print();
+    set_cursor_visible(true);
+}
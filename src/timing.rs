@@ -1,8 +1,99 @@
-use crate::{bernoulli::Bar as BernoulliBar, clamping, mapping, Baring};
-use std::{
+//! Bars approximating their remaining time.
+//!
+//! Time is measured by a clock, which is a function returning the time since an arbitrary, but fixed point in time.
+//! With feature `std`, the clock `std_clock` is used by default.
+//! Without, e.g. on embedded targets, a clock (e.g. reading a hardware-timer) has to be provided via `timed_by(...)`.
+
+#[cfg(feature = "std")]
+use crate::nesting;
+use crate::{
+    bernoulli::Bar as BernoulliBar,
+    clamping::{self, display_width, FmtFn},
+    mapping, Baring, State, Surrounding,
+};
+use alloc::{boxed::Box, string::String};
+use core::{
+    cmp::min,
     fmt::{self, Display},
-    time::Instant,
+    time::Duration,
 };
+use log::warn;
+
+/// The clock of timed bars with feature `std`, based on `std::time::Instant`.
+#[cfg(feature = "std")]
+pub fn std_clock() -> Duration {
+    use std::{sync::OnceLock, time::Instant};
+
+    static ORIGIN: OnceLock<Instant> = OnceLock::new();
+    ORIGIN.get_or_init(Instant::now).elapsed()
+}
+
+/// Number of rates kept by a timed bar, e.g. for `mini::sparkline(...)`
+pub const HISTORY_LEN: usize = 16;
+
+/// Rates of progress per interval, the oldest first
+#[derive(Debug)]
+struct History {
+    /// Fractions per second
+    rates: [f64; HISTORY_LEN],
+    len: usize,
+    interval: Duration,
+    /// Running time when the current interval has started
+    sample_started: Duration,
+    /// Fraction when the current interval has started
+    sample_fraction: f64,
+}
+
+impl History {
+    fn new() -> History {
+        History {
+            rates: [0.0; HISTORY_LEN],
+            len: 0,
+            interval: Duration::from_secs(1),
+            sample_started: Duration::from_secs(0),
+            sample_fraction: 0.0,
+        }
+    }
+
+    /// Adds the rate since the last sample for every interval passed since then.
+    fn sample(&mut self, running_time: Duration, fraction: f64) {
+        let span = running_time.saturating_sub(self.sample_started);
+        if span < self.interval {
+            return;
+        }
+
+        let span_s = span.as_secs_f64();
+        let rate = (fraction - self.sample_fraction) / span_s;
+        let intervals = (span_s / self.interval.as_secs_f64()) as usize;
+        for _ in 0..min(intervals, HISTORY_LEN) {
+            self.push(rate);
+        }
+        self.restart(running_time, fraction);
+    }
+
+    fn push(&mut self, rate: f64) {
+        if self.len == HISTORY_LEN {
+            self.rates.copy_within(1.., 0);
+            self.len -= 1;
+        }
+        self.rates[self.len] = rate;
+        self.len += 1;
+    }
+
+    fn restart(&mut self, running_time: Duration, fraction: f64) {
+        self.sample_started = running_time;
+        self.sample_fraction = fraction;
+    }
+}
+
+/// The callback of a stalled bar, see `set_on_stall(...)`
+struct OnStall(Box<dyn FnMut(Duration) + Send>);
+
+impl fmt::Debug for OnStall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("OnStall")
+    }
+}
 
 #[derive(Debug)]
 pub struct Bar<B>
@@ -10,21 +101,221 @@ where
     B: Baring,
 {
     bar: B,
-    now: Instant,
+    /// Returns the time since an arbitrary, but fixed point in time
+    clock: fn() -> Duration,
+    /// Returns the bar's progress mapped to `[0, 1]`
+    fraction: fn(&B) -> f64,
+    history: History,
+    /// The clock's time when starting
+    started: Duration,
+    /// Time spent before starting, e.g. before resuming from a checkpoint
+    prior_elapsed: Duration,
+    /// Frozen as soon as the bar is done
+    total_duration: Option<Duration>,
     is_remembering_progress: bool,
+    /// Running time without progress, after which the bar is stalled
+    stall_timeout: Option<Duration>,
+    /// Called once per stall
+    on_stall: Option<OnStall>,
+    /// Running time when the fraction has changed the last time
+    last_progressed: Duration,
+    last_fraction: f64,
+    /// Whether the current stall has been printed, see `has_progressed_significantly()`
+    is_stall_shown: bool,
+    /// Whether the current stall has been returned by `take_stall()`
+    is_stall_reported: bool,
 }
 
 impl<B> Bar<B>
 where
     B: Baring,
 {
-    pub(crate) fn with(bar: B) -> Bar<B> {
+    pub(crate) fn with(bar: B, clock: fn() -> Duration, fraction: fn(&B) -> f64) -> Bar<B> {
+        let initial_fraction = fraction(&bar);
+        let mut history = History::new();
+        history.restart(Duration::from_secs(0), initial_fraction);
         Bar {
             bar,
-            now: Instant::now(),
+            clock,
+            fraction,
+            history,
+            started: clock(),
+            prior_elapsed: Duration::from_secs(0),
+            total_duration: None,
             is_remembering_progress: false,
+            stall_timeout: None,
+            on_stall: None,
+            last_progressed: Duration::from_secs(0),
+            last_fraction: initial_fraction,
+            is_stall_shown: false,
+            is_stall_reported: false,
+        }
+    }
+
+    pub fn inner(&self) -> &B {
+        &self.bar
+    }
+
+    /// Gives access to the wrapped bar, e.g. for adding children to a `nesting::Bar`.
+    ///
+    /// If the wrapped bar becomes done this way, the elapsed time is frozen at the next call of `remember_significant_progress()`.
+    pub fn inner_mut(&mut self) -> &mut B {
+        &mut self.bar
+    }
+
+    /// Returns the time since creation (including time set via `set_elapsed(...)`), or the total duration if the bar is done.
+    pub fn elapsed(&self) -> Duration {
+        self.total_duration
+            .unwrap_or_else(|| self.prior_elapsed + self.running_time())
+    }
+
+    /// Returns the time since starting (or since `set_elapsed(...)`).
+    fn running_time(&self) -> Duration {
+        (self.clock)().saturating_sub(self.started)
+    }
+
+    /// Sets the time spent so far, e.g. when resuming a job.
+    /// The approximated remaining time takes this time into account.
+    pub fn set_elapsed(&mut self, elapsed: Duration) {
+        self.started = (self.clock)();
+        self.prior_elapsed = elapsed;
+        if self.total_duration.is_some() {
+            self.total_duration = Some(elapsed);
+        }
+        self.history
+            .restart(Duration::from_secs(0), (self.fraction)(&self.bar));
+        self.last_progressed = Duration::from_secs(0);
+    }
+
+    /// Returns the recent rates of progress (as fraction of the whole bar per second), the oldest first.
+    /// Every rate covers one interval (see `set_sample_interval(...)`), and at most `HISTORY_LEN` rates are kept.
+    pub fn rates(&self) -> &[f64] {
+        &self.history.rates[..self.history.len]
+    }
+
+    /// Replaces the recent rates, e.g. when resuming a job, keeping the latest `HISTORY_LEN` rates.
+    #[cfg(feature = "std")]
+    pub(crate) fn set_rates(&mut self, rates: &[f64]) {
+        self.history.len = 0;
+        for &rate in rates.iter().rev().take(HISTORY_LEN).rev() {
+            self.history.push(rate);
         }
     }
+
+    pub fn sample_interval(&self) -> Duration {
+        self.history.interval
+    }
+
+    /// Sets the time covered by every rate of `rates()`, which is `1 s` by default.
+    ///
+    /// panics if the interval is zero
+    pub fn set_sample_interval(&mut self, interval: Duration) {
+        assert!(
+            interval > Duration::from_secs(0),
+            "The interval has to be positive."
+        );
+        self.history.interval = interval;
+    }
+
+    /// Marks the bar as stalled if it hasn't progressed for the given time, or never if `None` (default).
+    ///
+    /// A stalled bar shows for how long it has stalled instead of its remaining time, which is unknown.
+    /// A stall counts as significant progress, hence it is printed at least once.
+    /// Once per stall, `take_stall()` logs a warning and calls the callback (see `set_on_stall(...)`).
+    /// Printing alone doesn't warn: a `ticking::Ticker` and the iterators of feature `rayon` call `take_stall()` after every print,
+    /// otherwise it has to be called after printing (and after unlocking a printer shared with a `logging::Logger`).
+    ///
+    ///
+    /// # Mini-Example
+    ///
+    /// ```
+    /// use progressing::{clamping::Bar as ClampingBar, Baring};
+    /// use std::{
+    ///     sync::atomic::{AtomicU64, Ordering},
+    ///     time::Duration,
+    /// };
+    ///
+    /// static NOW_S: AtomicU64 = AtomicU64::new(0);
+    ///
+    /// fn clock() -> Duration {
+    ///     Duration::from_secs(NOW_S.load(Ordering::Relaxed))
+    /// }
+    ///
+    /// fn main() {
+    ///     let mut progress_bar = ClampingBar::new().timed_by(clock);
+    ///     progress_bar.set_len(20);
+    ///     progress_bar.set_stall_timeout(Some(Duration::from_secs(60)));
+    ///
+    ///     NOW_S.store(10, Ordering::Relaxed);
+    ///     progress_bar.set(0.3);
+    ///     assert_eq!(progress_bar.stalled_for(), None);
+    ///
+    ///     // no progress for more than a minute
+    ///     NOW_S.store(200, Ordering::Relaxed);
+    ///     assert_eq!(progress_bar.stalled_for(), Some(Duration::from_secs(190)));
+    ///     assert_eq!(progress_bar.to_string(), "[=====>............] stalled for 3min");
+    ///
+    ///     // warning once per stall
+    ///     assert_eq!(progress_bar.take_stall(), Some(Duration::from_secs(190)));
+    ///     assert_eq!(progress_bar.take_stall(), None);
+    /// }
+    /// ```
+    pub fn set_stall_timeout(&mut self, stall_timeout: Option<Duration>) {
+        self.stall_timeout = stall_timeout;
+    }
+
+    /// Sets a function called with the stalled time once per stall, see `take_stall()`.
+    /// It may capture state, e.g. a channel notifying another thread.
+    pub fn set_on_stall<F>(&mut self, on_stall: F)
+    where
+        F: FnMut(Duration) + Send + 'static,
+    {
+        self.on_stall = Some(OnStall(Box::new(on_stall)));
+    }
+
+    /// Returns the time without progress, if the running bar has stalled (see `set_stall_timeout(...)`).
+    /// Printing a stalled bar shows the stall, but doesn't warn, see `set_stall_timeout(...)`.
+    pub fn stalled_for(&self) -> Option<Duration> {
+        let stall_timeout = self.stall_timeout?;
+        if self.state() != State::Running || self.has_progressed_since_tracked() {
+            return None;
+        }
+
+        let stalled = self.running_time().saturating_sub(self.last_progressed);
+        Some(stalled).filter(|&stalled| stalled >= stall_timeout)
+    }
+
+    fn has_progressed_since_tracked(&self) -> bool {
+        (self.fraction)(&self.bar) != self.last_fraction
+    }
+
+    /// Remembers the time of the latest progress, e.g. set via `inner_mut()`.
+    fn track_progress(&mut self) {
+        if self.has_progressed_since_tracked() {
+            self.last_fraction = (self.fraction)(&self.bar);
+            self.last_progressed = self.running_time();
+            self.is_stall_shown = false;
+            self.is_stall_reported = false;
+        }
+    }
+
+    /// Shows the inner bar as stalled (see `Baring::set_stalled(...)`) while `stalled_for()` reports a stall.
+    fn update_stalled(&mut self) {
+        let is_stalled = self.stalled_for().is_some();
+        self.bar.set_stalled(is_stalled);
+    }
+
+    /// Returns the total duration if the bar is done (e.g. ` done in 12s`), the stalled time if stalled (e.g. ` stalled for 3min`),
+    /// or the approximated remaining time otherwise.
+    fn time(&self, approx_secs: Option<usize>) -> impl Display {
+        let state = self.state();
+        let total = self.elapsed().as_secs() as usize;
+        let stalled_secs = self.stalled_for().map(|stalled| stalled.as_secs() as usize);
+        FmtFn(move |f| match stalled_secs {
+            Some(secs) => write!(f, " stalled for {}", format_secs(secs)),
+            None => fmt_time(f, state, total, approx_secs),
+        })
+    }
 }
 
 impl<B> Baring for Bar<B>
@@ -42,6 +333,10 @@ where
         self.bar.set_len(new_bar_len);
     }
 
+    fn fit_terminal(&mut self, min_bar_len: usize) {
+        self.bar.fit_terminal(min_bar_len)
+    }
+
     fn progress(&self) -> Self::Progress {
         self.bar.progress()
     }
@@ -50,7 +345,12 @@ where
     where
         P: Into<Self::Progress>,
     {
-        self.bar.set(new_progress)
+        self.bar.set(new_progress);
+        let running_time = self.running_time();
+        self.history
+            .sample(running_time, (self.fraction)(&self.bar));
+        self.track_progress();
+        self.update_stalled();
     }
 
     fn start(&self) -> Self::Progress {
@@ -63,48 +363,155 @@ where
 
     fn has_progressed_significantly(&self) -> bool {
         self.bar.has_progressed_significantly()
-            || (!self.is_remembering_progress && (self.now.elapsed().as_millis() > 60_000))
+            || (!self.is_remembering_progress && (self.running_time().as_millis() > 60_000))
+            || (!self.is_stall_shown && self.stalled_for().is_some())
     }
 
+    /// A stall (see `set_stall_timeout(...)`) is worth printing once.
     fn remember_significant_progress(&mut self) {
         self.bar.remember_significant_progress();
+        self.track_progress();
+        self.is_stall_shown = self.stalled_for().is_some();
+        self.update_stalled();
         self.is_remembering_progress = true;
+        if self.state().is_done() && self.total_duration.is_none() {
+            self.total_duration = Some(self.elapsed());
+        }
+    }
+
+    fn state(&self) -> State {
+        self.bar.state()
+    }
+
+    fn set_state(&mut self, new_state: State) {
+        if new_state.is_done() {
+            if self.total_duration.is_none() {
+                self.total_duration = Some(self.elapsed());
+            }
+        } else {
+            self.total_duration = None;
+        }
+        self.bar.set_state(new_state);
+        self.update_stalled();
+    }
+
+    fn set_prefix<S>(&mut self, prefix: S)
+    where
+        S: Into<String>,
+    {
+        self.bar.set_prefix(prefix)
+    }
+
+    fn set_message<S>(&mut self, msg: S)
+    where
+        S: Into<String>,
+    {
+        self.bar.set_message(msg)
+    }
+
+    fn tick(&mut self) {
+        self.bar.tick();
+        self.update_stalled();
+    }
+
+    fn set_stalled(&mut self, is_stalled: bool) {
+        self.bar.set_stalled(is_stalled)
+    }
+
+    /// Logs a warning and calls the callback (see `set_on_stall(...)`), if the bar has stalled and this stall hasn't been taken yet.
+    fn take_stall(&mut self) -> Option<Duration> {
+        self.track_progress();
+        self.update_stalled();
+        if self.is_stall_reported {
+            return None;
+        }
+
+        let stalled = self.stalled_for()?;
+        self.is_stall_reported = true;
+        warn!(
+            "The bar has stalled, having made no progress for {}.",
+            format_secs(stalled.as_secs() as usize)
+        );
+        if let Some(OnStall(on_stall)) = &mut self.on_stall {
+            on_stall(stalled);
+        }
+        Some(stalled)
     }
 }
 
+/// Writes the total duration if done (e.g. ` done in 12s`), or the approximated remaining time otherwise (e.g. ` ~3min`).
+pub(crate) fn fmt_time(
+    f: &mut fmt::Formatter<'_>,
+    state: State,
+    total_secs: usize,
+    approx_secs: Option<usize>,
+) -> fmt::Result {
+    match (state, approx_secs) {
+        (State::Running, Some(secs)) => {
+            // padded to a fixed width, e.g. `   9s` or `59min`
+            let approx = format_secs(secs);
+            f.write_str(" ~")?;
+            for _ in display_width(&approx)..5 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{}", approx)
+        }
+        (State::Running, None) => write!(f, " ~inf s"),
+        (State::Finished, _) => write!(f, " done in {}", format_secs(total_secs)),
+        (State::Abandoned, _) => write!(f, " abandoned after {}", format_secs(total_secs)),
+        (State::Failed, _) => write!(f, " failed after {}", format_secs(total_secs)),
+    }
+}
+
+fn format_secs(secs: usize) -> impl Display {
+    let mut elapsed = secs;
+    let mut unit = "s";
+
+    // update unit
+    if elapsed > 3_600 {
+        elapsed /= 3_600;
+        unit = "h";
+    } else if elapsed > 60 {
+        elapsed /= 60;
+        unit = "min";
+    }
+
+    FmtFn(move |f| write!(f, "{}{}", elapsed, unit))
+}
+
 //------------------------------------------------------------------------------------------------//
 // displaying time
 
 impl Bar<clamping::Bar> {
-    fn approx_time(&self) -> String {
+    fn approx_secs(&self) -> Option<usize> {
         let progress = self.progress();
         if progress > self.start() {
             let scale = (self.end() - progress) / progress;
 
-            let elapsed_ms = self.now.elapsed().as_millis();
+            let elapsed_ms = self.elapsed().as_millis();
             let elapsed_s = elapsed_ms as f64 / 1_000.0;
-            let mut elapsed = (elapsed_s * scale) as usize;
-            let mut unit = "s";
-
-            // update unit
-            if elapsed > 3_600 {
-                elapsed /= 3_600;
-                unit = "h";
-            } else if elapsed > 60 {
-                elapsed /= 60;
-                unit = "min";
-            }
-
-            format!("{}{}", elapsed, unit)
+            Some((elapsed_s * scale) as usize)
         } else {
-            String::from("inf s")
+            None
         }
     }
 }
 
+impl Surrounding for Bar<clamping::Bar> {
+    fn fmt_surrounded<L, T>(&self, f: &mut fmt::Formatter<'_>, lead: L, trail: T) -> fmt::Result
+    where
+        L: Display,
+        T: Display,
+    {
+        let time = self.time(self.approx_secs());
+        self.bar
+            .fmt_line_with(f, lead, format_args!("{}{}", time, trail))
+    }
+}
+
 impl Display for Bar<clamping::Bar> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{} ~{}", self.bar, self.approx_time())
+        self.fmt_surrounded(f, "", "")
     }
 }
 
@@ -112,38 +519,91 @@ impl Bar<mapping::Bar<usize>>
 where
     mapping::Bar<usize>: Baring,
 {
-    fn approx_time(&self) -> String {
+    fn approx_secs(&self) -> Option<usize> {
         let progress = self.progress();
         if progress > self.start() {
             let scale = ((self.end() - progress) as f64) / (progress as f64);
 
-            let elapsed_ms = self.now.elapsed().as_millis();
+            let elapsed_ms = self.elapsed().as_millis();
             let elapsed_s = elapsed_ms as f64 / 1_000.0;
-            let mut elapsed = (elapsed_s * scale) as usize;
-            let mut unit = "s";
-
-            // update unit
-            if elapsed > 3_600 {
-                elapsed /= 3_600;
-                unit = "h";
-            } else if elapsed > 60 {
-                elapsed /= 60;
-                unit = "min";
-            }
-
-            format!("{}{}", elapsed, unit)
+            Some((elapsed_s * scale) as usize)
         } else {
-            String::from("inf s")
+            None
         }
     }
 }
 
+impl Surrounding for Bar<mapping::Bar<usize>>
+where
+    mapping::Bar<usize>: Baring,
+{
+    fn fmt_surrounded<L, T>(&self, f: &mut fmt::Formatter<'_>, lead: L, trail: T) -> fmt::Result
+    where
+        L: Display,
+        T: Display,
+    {
+        let rate = self.bar.rate(self.elapsed());
+        let time = self.time(self.approx_secs());
+        self.bar.bar.fmt_line_with(
+            f,
+            lead,
+            format_args!("{}{}{}{}", self.bar.suffix(), rate, time, trail),
+        )
+    }
+}
+
 impl Display for Bar<mapping::Bar<usize>>
 where
     mapping::Bar<usize>: Baring,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{} ~{}", self.bar, self.approx_time())
+        self.fmt_surrounded(f, "", "")
+    }
+}
+
+impl Bar<mapping::Bar<u64>>
+where
+    mapping::Bar<u64>: Baring,
+{
+    fn approx_secs(&self) -> Option<usize> {
+        let progress = self.progress();
+        if progress > self.start() {
+            let scale = ((self.end() - progress) as f64) / (progress as f64);
+
+            let elapsed_ms = self.elapsed().as_millis();
+            let elapsed_s = elapsed_ms as f64 / 1_000.0;
+            Some((elapsed_s * scale) as usize)
+        } else {
+            None
+        }
+    }
+}
+
+impl Surrounding for Bar<mapping::Bar<u64>>
+where
+    mapping::Bar<u64>: Baring,
+{
+    fn fmt_surrounded<L, T>(&self, f: &mut fmt::Formatter<'_>, lead: L, trail: T) -> fmt::Result
+    where
+        L: Display,
+        T: Display,
+    {
+        let rate = self.bar.rate(self.elapsed());
+        let time = self.time(self.approx_secs());
+        self.bar.bar.fmt_line_with(
+            f,
+            lead,
+            format_args!("{}{}{}{}", self.bar.suffix(), rate, time, trail),
+        )
+    }
+}
+
+impl Display for Bar<mapping::Bar<u64>>
+where
+    mapping::Bar<u64>: Baring,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_surrounded(f, "", "")
     }
 }
 
@@ -151,38 +611,45 @@ impl Bar<mapping::Bar<i64>>
 where
     mapping::Bar<i64>: Baring,
 {
-    fn approx_time(&self) -> String {
+    fn approx_secs(&self) -> Option<usize> {
         let progress = self.progress();
         if progress > self.start() {
             let scale = ((self.end() - progress) as f64) / (progress as f64);
 
-            let elapsed_ms = self.now.elapsed().as_millis();
+            let elapsed_ms = self.elapsed().as_millis();
             let elapsed_s = elapsed_ms as f64 / 1_000.0;
-            let mut elapsed = (elapsed_s * scale) as usize;
-            let mut unit = "s";
-
-            // update unit
-            if elapsed > 3_600 {
-                elapsed /= 3_600;
-                unit = "h";
-            } else if elapsed > 60 {
-                elapsed /= 60;
-                unit = "min";
-            }
-
-            format!("{}{}", elapsed, unit)
+            Some((elapsed_s * scale) as usize)
         } else {
-            String::from("inf s")
+            None
         }
     }
 }
 
+impl Surrounding for Bar<mapping::Bar<i64>>
+where
+    mapping::Bar<i64>: Baring,
+{
+    fn fmt_surrounded<L, T>(&self, f: &mut fmt::Formatter<'_>, lead: L, trail: T) -> fmt::Result
+    where
+        L: Display,
+        T: Display,
+    {
+        let rate = self.bar.rate(self.elapsed());
+        let time = self.time(self.approx_secs());
+        self.bar.bar.fmt_line_with(
+            f,
+            lead,
+            format_args!("{}{}{}{}", self.bar.suffix(), rate, time, trail),
+        )
+    }
+}
+
 impl Display for Bar<mapping::Bar<i64>>
 where
     mapping::Bar<i64>: Baring,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{} ~{}", self.bar, self.approx_time())
+        self.fmt_surrounded(f, "", "")
     }
 }
 
@@ -190,38 +657,45 @@ impl Bar<mapping::Bar<i32>>
 where
     mapping::Bar<i32>: Baring,
 {
-    fn approx_time(&self) -> String {
+    fn approx_secs(&self) -> Option<usize> {
         let progress = self.progress();
         if progress > self.start() {
             let scale = ((self.end() - progress) as f64) / (progress as f64);
 
-            let elapsed_ms = self.now.elapsed().as_millis();
+            let elapsed_ms = self.elapsed().as_millis();
             let elapsed_s = elapsed_ms as f64 / 1_000.0;
-            let mut elapsed = (elapsed_s * scale) as usize;
-            let mut unit = "s";
-
-            // update unit
-            if elapsed > 3_600 {
-                elapsed /= 3_600;
-                unit = "h";
-            } else if elapsed > 60 {
-                elapsed /= 60;
-                unit = "min";
-            }
-
-            format!("{}{}", elapsed, unit)
+            Some((elapsed_s * scale) as usize)
         } else {
-            String::from("inf s")
+            None
         }
     }
 }
 
+impl Surrounding for Bar<mapping::Bar<i32>>
+where
+    mapping::Bar<i32>: Baring,
+{
+    fn fmt_surrounded<L, T>(&self, f: &mut fmt::Formatter<'_>, lead: L, trail: T) -> fmt::Result
+    where
+        L: Display,
+        T: Display,
+    {
+        let rate = self.bar.rate(self.elapsed());
+        let time = self.time(self.approx_secs());
+        self.bar.bar.fmt_line_with(
+            f,
+            lead,
+            format_args!("{}{}{}{}", self.bar.suffix(), rate, time, trail),
+        )
+    }
+}
+
 impl Display for Bar<mapping::Bar<i32>>
 where
     mapping::Bar<i32>: Baring,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{} ~{}", self.bar, self.approx_time())
+        self.fmt_surrounded(f, "", "")
     }
 }
 
@@ -229,7 +703,7 @@ impl Bar<BernoulliBar>
 where
     BernoulliBar: Baring,
 {
-    fn approx_time(&self) -> String {
+    fn approx_secs(&self) -> Option<usize> {
         let progress = self.progress();
         if progress.successes > self.start().successes {
             let scale = {
@@ -241,32 +715,74 @@ where
                 }
             };
 
-            let elapsed_ms = self.now.elapsed().as_millis();
+            let elapsed_ms = self.elapsed().as_millis();
             let elapsed_s = elapsed_ms as f64 / 1_000.0;
-            let mut elapsed = (elapsed_s * scale) as usize;
-            let mut unit = "s";
-
-            // update unit
-            if elapsed > 3_600 {
-                elapsed /= 3_600;
-                unit = "h";
-            } else if elapsed > 60 {
-                elapsed /= 60;
-                unit = "min";
-            }
-
-            format!("{}{}", elapsed, unit)
+            Some((elapsed_s * scale) as usize)
         } else {
-            String::from("inf s")
+            None
         }
     }
 }
 
+impl Surrounding for Bar<BernoulliBar>
+where
+    BernoulliBar: Baring,
+{
+    fn fmt_surrounded<L, T>(&self, f: &mut fmt::Formatter<'_>, lead: L, trail: T) -> fmt::Result
+    where
+        L: Display,
+        T: Display,
+    {
+        let time = self.time(self.approx_secs());
+        self.bar.bar.bar.fmt_line_with(
+            f,
+            lead,
+            format_args!("{}{}{}", self.bar.suffix(), time, trail),
+        )
+    }
+}
+
 impl Display for Bar<BernoulliBar>
 where
     BernoulliBar: Baring,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{} ~{}", self.bar, self.approx_time())
+        self.fmt_surrounded(f, "", "")
+    }
+}
+
+#[cfg(feature = "std")]
+impl Bar<nesting::Bar> {
+    fn approx_secs(&self) -> Option<usize> {
+        let progress = self.progress();
+        if progress > self.start() {
+            let scale = (self.end() - progress) / progress;
+
+            let elapsed_ms = self.elapsed().as_millis();
+            let elapsed_s = elapsed_ms as f64 / 1_000.0;
+            Some((elapsed_s * scale) as usize)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Surrounding for Bar<nesting::Bar> {
+    fn fmt_surrounded<L, T>(&self, f: &mut fmt::Formatter<'_>, lead: L, trail: T) -> fmt::Result
+    where
+        L: Display,
+        T: Display,
+    {
+        let time = self.time(self.approx_secs());
+        self.bar
+            .fmt_surrounded(f, lead, format_args!("{}{}", time, trail))
+    }
+}
+
+#[cfg(feature = "std")]
+impl Display for Bar<nesting::Bar> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_surrounded(f, "", "")
     }
 }
@@ -0,0 +1,306 @@
+//! Integration of the `tracing`-crate (feature `tracing`), creating bars from spans.
+//!
+//! A span carrying the field `progress.total` at its creation gets its own bar, named after the span.
+//! Events inside the span update the bar via the fields `progress.inc` (adding) or `progress.set` (setting), and `progress.message` sets the bar's message.
+//! Spans inside spans with bars are shown as indented sub-bars.
+//! When a span closes, its bar is finished and printed above the bars still running.
+
+use crate::{
+    mapping,
+    printing::{Mode, Printer},
+    timing, Baring,
+};
+use std::{
+    fmt::{self, Debug},
+    io::Write,
+    sync::{Arc, Mutex, PoisonError},
+};
+use tracing_core::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    Event, Subscriber,
+};
+use tracing_subscriber::{layer::Context, registry::LookupSpan, Layer as Layering};
+
+type SpanBar = timing::Bar<mapping::Bar<usize>>;
+
+/// The progress-related fields of a span or an event.
+#[derive(Default)]
+struct Fields {
+    total: Option<usize>,
+    inc: Option<usize>,
+    set: Option<usize>,
+    message: Option<String>,
+}
+
+impl Visit for Fields {
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        match field.name() {
+            "progress.total" => self.total = Some(value as usize),
+            "progress.inc" => self.inc = Some(value as usize),
+            "progress.set" => self.set = Some(value as usize),
+            "progress.message" => self.message = Some(value.to_string()),
+            _ => (),
+        }
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        if value >= 0 {
+            self.record_u64(field, value as u64);
+        } else if field.name() == "progress.message" {
+            self.message = Some(value.to_string());
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "progress.message" {
+            self.message = Some(String::from(value));
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if field.name() == "progress.message" {
+            self.message = Some(format!("{:?}", value));
+        }
+    }
+}
+
+impl Fields {
+    fn has_updates(&self) -> bool {
+        self.inc.is_some() || self.set.is_some() || self.message.is_some()
+    }
+
+    fn apply_to(self, bar: &mut SpanBar) {
+        if let Some(value) = self.set {
+            bar.set(value);
+        }
+        if let Some(delta) = self.inc {
+            bar.add(delta);
+        }
+        if let Some(msg) = self.message {
+            bar.set_message(msg);
+        }
+    }
+}
+
+struct Entry {
+    id: Id,
+    /// Number of ancestors having bars
+    depth: usize,
+    bar: SpanBar,
+}
+
+struct Bars<W>
+where
+    W: Write + Send,
+{
+    /// In tree-order, meaning children follow their parent
+    entries: Vec<Entry>,
+    printer: Arc<Mutex<Printer<W>>>,
+}
+
+impl<W> Bars<W>
+where
+    W: Write + Send,
+{
+    fn position(&self, id: &Id) -> Option<usize> {
+        self.entries.iter().position(|entry| &entry.id == id)
+    }
+
+    /// Inserts the entry after the last descendant of its parent, or at the end if it has no parent.
+    fn insert(&mut self, parent: Option<&Id>, entry: Entry) {
+        let idx = match parent.and_then(|parent| self.position(parent)) {
+            Some(parent_idx) => {
+                let parent_depth = self.entries[parent_idx].depth;
+                let descendant_count = self.entries[parent_idx + 1..]
+                    .iter()
+                    .take_while(|entry| entry.depth > parent_depth)
+                    .count();
+                parent_idx + 1 + descendant_count
+            }
+            None => self.entries.len(),
+        };
+        self.entries.insert(idx, entry);
+    }
+
+    /// Prints the bars after the bar at the given position has been updated.
+    ///
+    /// Interactively, all bars are redrawn.
+    /// Otherwise, only the updated bar is printed if it has progressed significantly.
+    fn print(&mut self, idx: usize) {
+        let printer = Arc::clone(&self.printer);
+        let mut printer = printer.lock().unwrap_or_else(PoisonError::into_inner);
+        let bar = &mut self.entries[idx].bar;
+        let is_significant = bar.has_progressed_significantly();
+        if is_significant {
+            bar.remember_significant_progress();
+        }
+
+        // Tracing must not panic, hence errors are dropped.
+        let _ = if printer.mode() == Mode::Interactive {
+            printer.draw(self)
+        } else if is_significant {
+            printer.println(&self.entries[idx].bar)
+        } else {
+            Ok(())
+        };
+    }
+}
+
+impl<W> fmt::Display for Bars<W>
+where
+    W: Write + Send,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, entry) in self.entries.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", entry.bar)?;
+        }
+        Ok(())
+    }
+}
+
+/// A `tracing_subscriber::Layer` showing progress of spans as bars via the given `Printer`.
+///
+///
+/// # Mini-Example
+///
+/// ```
+/// use progressing::{
+///     printing::{Mode, Printer},
+///     tracing::Layer as ProgressLayer,
+/// };
+/// use std::sync::Arc;
+/// use tracing::{info, info_span};
+/// use tracing_subscriber::{layer::SubscriberExt, Registry};
+///
+/// fn main() {
+///     let printer = Printer::with(Vec::new(), Mode::Lines).shared();
+///     let subscriber = Registry::default().with(ProgressLayer::new(Arc::clone(&printer)));
+///     tracing::subscriber::with_default(subscriber, || {
+///         let span = info_span!("downloading", progress.total = 10);
+///         let _guard = span.enter();
+///         for i in 1..=10 {
+///             info!(progress.inc = 1, progress.message = i);
+///         }
+///     });
+///
+///     let printer = Arc::try_unwrap(printer).unwrap().into_inner().unwrap();
+///     let output = String::from_utf8(printer.into_inner()).unwrap();
+///     // the message follows the bar
+///     let last_line = output.lines().last().unwrap();
+///     assert!(last_line.contains("(10/10)") && last_line.ends_with(" 10"));
+/// }
+/// ```
+pub struct Layer<W>
+where
+    W: Write + Send,
+{
+    bars: Mutex<Bars<W>>,
+}
+
+impl<W> Layer<W>
+where
+    W: Write + Send,
+{
+    pub fn new(printer: Arc<Mutex<Printer<W>>>) -> Layer<W> {
+        Layer {
+            bars: Mutex::new(Bars {
+                entries: Vec::new(),
+                printer,
+            }),
+        }
+    }
+}
+
+impl<S, W> Layering<S> for Layer<W>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: Write + Send + 'static,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut fields = Fields::default();
+        attrs.record(&mut fields);
+        let total = match fields.total {
+            Some(total) => total,
+            None => return,
+        };
+
+        let mut bars = self.bars.lock().unwrap_or_else(PoisonError::into_inner);
+
+        // the nearest ancestor having a bar is the parent
+        let ancestors: Vec<Id> = match ctx.span(id) {
+            Some(span) => span.scope().skip(1).map(|span| span.id()).collect(),
+            None => Vec::new(),
+        };
+        let parent = ancestors.iter().find(|id| bars.position(id).is_some());
+        let depth = ancestors
+            .iter()
+            .filter(|id| bars.position(id).is_some())
+            .count();
+
+        let mut bar = mapping::Bar::with_range(0, total).timed();
+        bar.set_prefix(format!("{}{}", "  ".repeat(depth), attrs.metadata().name()));
+        bar.fit_terminal(10);
+        fields.apply_to(&mut bar);
+
+        let entry = Entry {
+            id: id.clone(),
+            depth,
+            bar,
+        };
+        bars.insert(parent, entry);
+        if let Some(idx) = bars.position(id) {
+            bars.print(idx);
+        }
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, _ctx: Context<'_, S>) {
+        let mut fields = Fields::default();
+        values.record(&mut fields);
+
+        let mut bars = self.bars.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(idx) = bars.position(id) {
+            fields.apply_to(&mut bars.entries[idx].bar);
+            bars.print(idx);
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut fields = Fields::default();
+        event.record(&mut fields);
+        if !fields.has_updates() {
+            return;
+        }
+
+        let mut bars = self.bars.lock().unwrap_or_else(PoisonError::into_inner);
+        // the nearest span having a bar is updated
+        let idx = ctx.event_scope(event).and_then(|scope| {
+            scope
+                .map(|span| span.id())
+                .find_map(|id| bars.position(&id))
+        });
+        if let Some(idx) = idx {
+            fields.apply_to(&mut bars.entries[idx].bar);
+            bars.print(idx);
+        }
+    }
+
+    fn on_close(&self, id: Id, _ctx: Context<'_, S>) {
+        let mut bars = self.bars.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(idx) = bars.position(&id) {
+            let mut entry = bars.entries.remove(idx);
+            entry.bar.finish();
+
+            let mut printer = bars.printer.lock().unwrap_or_else(PoisonError::into_inner);
+            // Tracing must not panic, hence errors are dropped.
+            let _ = printer.clear();
+            let _ = printer.println(&entry.bar);
+            if !bars.entries.is_empty() {
+                let _ = printer.draw(&*bars);
+            }
+        }
+    }
+}
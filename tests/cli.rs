@@ -0,0 +1,56 @@
+// The binary is only built with feature cli.
+#![cfg(feature = "cli")]
+
+use std::{
+    io::Write,
+    process::{Command, Output, Stdio},
+    thread,
+};
+
+fn run(args: &[&str], stdin: Vec<u8>) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_progressing"))
+        .args(args)
+        .env("PROGRESSING_MODE", "lines")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // writing in parallel, since the piped stdout would fill up otherwise
+    let mut child_stdin = child.stdin.take().unwrap();
+    let writer = thread::spawn(move || child_stdin.write_all(&stdin));
+    let output = child.wait_with_output().unwrap();
+    writer.join().unwrap().unwrap();
+    output
+}
+
+#[test]
+fn pipe() {
+    let data: Vec<u8> = (0..100_000u32).flat_map(u32::to_le_bytes).collect();
+    let output = run(&["--size", "400000", "--width", "12"], data.clone());
+
+    assert!(output.status.success());
+    assert_eq!(output.stdout, data);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr
+        .trim_end()
+        .ends_with("[==========] (390.6 KiB / 390.6 KiB)"));
+}
+
+#[test]
+fn values() {
+    let output = run(&["values", "--width", "12"], b"1/4\n2/4\n\n4/4\n".to_vec());
+
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.trim_end().ends_with("[==========] (4/4)"));
+}
+
+#[test]
+fn invalid_values() {
+    let output = run(&["attempts", "--goal", "4"], b"1/2\nx\n".to_vec());
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("invalid line \"x\""));
+}
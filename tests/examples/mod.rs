@@ -12,6 +12,150 @@ fn loops() {
     example::test();
 }
 
+#[test]
+fn presets() {
+    #[allow(dead_code)]
+    mod example {
+        include!("../../examples/presets.rs");
+
+        pub fn test() {
+            main();
+        }
+    }
+
+    example::test();
+}
+
+#[test]
+fn effects() {
+    #[allow(dead_code)]
+    mod example {
+        include!("../../examples/effects.rs");
+
+        pub fn test() {
+            main();
+        }
+    }
+
+    example::test();
+}
+
+#[test]
+fn checkpoint() {
+    #[allow(dead_code)]
+    mod example {
+        include!("../../examples/checkpoint.rs");
+
+        pub fn test() {
+            main().unwrap();
+        }
+    }
+
+    example::test();
+}
+
+#[cfg(feature = "futures")]
+#[test]
+fn futures() {
+    #[allow(dead_code)]
+    mod example {
+        include!("../../examples/futures.rs");
+
+        pub fn test() {
+            main();
+        }
+    }
+
+    example::test();
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn json() {
+    #[allow(dead_code)]
+    mod example {
+        include!("../../examples/json.rs");
+
+        pub fn test() {
+            main().unwrap();
+        }
+    }
+
+    example::test();
+}
+
+#[test]
+fn logging() {
+    #[allow(dead_code)]
+    mod example {
+        include!("../../examples/logging.rs");
+
+        pub fn test() {
+            main().unwrap();
+        }
+    }
+
+    example::test();
+}
+
+#[test]
+fn nesting() {
+    #[allow(dead_code)]
+    mod example {
+        include!("../../examples/nesting.rs");
+
+        pub fn test() {
+            main().unwrap();
+        }
+    }
+
+    example::test();
+}
+
+#[test]
+fn printing() {
+    #[allow(dead_code)]
+    mod example {
+        include!("../../examples/printing.rs");
+
+        pub fn test() {
+            main().unwrap();
+        }
+    }
+
+    example::test();
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn rayon() {
+    #[allow(dead_code)]
+    mod example {
+        include!("../../examples/rayon.rs");
+
+        pub fn test() {
+            main();
+        }
+    }
+
+    example::test();
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde() {
+    #[allow(dead_code)]
+    mod example {
+        include!("../../examples/serde.rs");
+
+        pub fn test() {
+            main().unwrap();
+        }
+    }
+
+    example::test();
+}
+
 #[test]
 fn simple() {
     #[allow(dead_code)]
@@ -25,3 +169,32 @@ fn simple() {
 
     example::test();
 }
+
+#[test]
+fn staging() {
+    #[allow(dead_code)]
+    mod example {
+        include!("../../examples/staging.rs");
+
+        pub fn test() {
+            main().unwrap();
+        }
+    }
+
+    example::test();
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn tracing() {
+    #[allow(dead_code)]
+    mod example {
+        include!("../../examples/tracing.rs");
+
+        pub fn test() {
+            main();
+        }
+    }
+
+    example::test();
+}
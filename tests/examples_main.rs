@@ -1 +1,3 @@
+// The examples print to a terminal, hence they need std.
+#[cfg(feature = "std")]
 mod examples;
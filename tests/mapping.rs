@@ -0,0 +1,18 @@
+use progressing::{mapping::Bar as MappingBar, Baring};
+
+#[test]
+fn progress_below_start() {
+    let mut progress_bar = MappingBar::with_range(10u64, 20);
+    progress_bar.set(5u64);
+    assert_eq!(
+        progress_bar.to_string(),
+        "[>.......................................] ( 5/20)"
+    );
+
+    let mut progress_bar = MappingBar::with_range(-10i64, 10);
+    progress_bar.set(-20i64);
+    assert_eq!(
+        progress_bar.to_string(),
+        "[>.......................................] (-20/10)"
+    );
+}
@@ -0,0 +1,35 @@
+// Printers need std.
+#![cfg(feature = "std")]
+
+use progressing::{
+    clamping::{Bar as ClampingBar, Effect},
+    printing::{Mode, Printer},
+    Baring,
+};
+
+fn redraws(out: &[u8]) -> usize {
+    out.iter().filter(|&&byte| byte == b'\r').count()
+}
+
+#[test]
+fn insignificant_progress_is_not_redrawn() {
+    let mut printer = Printer::with(Vec::new(), Mode::Interactive);
+    let mut progress_bar = ClampingBar::new();
+    printer.print(&mut progress_bar).unwrap();
+    progress_bar.set(0.05);
+    printer.print(&mut progress_bar).unwrap();
+    assert_eq!(redraws(&printer.into_inner()), 1);
+}
+
+#[test]
+fn ticking_effects_are_redrawn() {
+    let mut printer = Printer::with(Vec::new(), Mode::Interactive);
+    printer.set_colored(true);
+    let mut progress_bar = ClampingBar::new();
+    progress_bar.set(0.5);
+    progress_bar.set_effect(Effect::Shimmer);
+    printer.print(&mut progress_bar).unwrap();
+    progress_bar.tick();
+    printer.print(&mut progress_bar).unwrap();
+    assert_eq!(redraws(&printer.into_inner()), 2);
+}
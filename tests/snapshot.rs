@@ -0,0 +1,42 @@
+// Checkpoints need std.
+#![cfg(feature = "std")]
+
+use progressing::{
+    mapping::Bar as MappingBar,
+    snapshot::{Snapshot, Snapshotting},
+    Baring, State,
+};
+use std::time::Duration;
+
+#[test]
+fn sub_millisecond_sample_interval() -> std::io::Result<()> {
+    let path = std::env::temp_dir().join("progressing-test-sub-millisecond.txt");
+
+    let mut progress_bar = MappingBar::with_range(0usize, 1_000).timed();
+    progress_bar.set_sample_interval(Duration::from_micros(250));
+    progress_bar.set(420usize);
+    progress_bar.checkpoint(&path)?;
+
+    let mut resumed_bar = MappingBar::with_range(0usize, 1_000).timed();
+    resumed_bar.resume(&path)?;
+    assert_eq!(resumed_bar.progress(), 420);
+    assert_eq!(resumed_bar.sample_interval(), Duration::from_micros(250));
+    Ok(())
+}
+
+#[test]
+fn zero_sample_interval() {
+    let mut progress_bar = MappingBar::with_range(0usize, 1_000).timed();
+    progress_bar.restore(&Snapshot {
+        start: 0,
+        end: 1_000,
+        progress: 420,
+        attempts: None,
+        elapsed: Some(Duration::from_secs(3)),
+        state: State::Running,
+        rates: Vec::new(),
+        sample_interval: Some(Duration::from_secs(0)),
+    });
+    assert_eq!(progress_bar.progress(), 420);
+    assert_eq!(progress_bar.sample_interval(), Duration::from_secs(1));
+}
@@ -0,0 +1,156 @@
+// The logger and the printer need std.
+#![cfg(feature = "std")]
+
+use log::{warn, LevelFilter};
+use progressing::{
+    clamping::{Bar as ClampingBar, Effect},
+    logging::Logger,
+    printing::{Mode, Printer},
+    Baring,
+};
+use std::{
+    fmt::{self, Display},
+    io::{self, Write},
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        mpsc, Arc, Mutex, OnceLock,
+    },
+    thread,
+    time::Duration,
+};
+
+static NOW_S: AtomicU64 = AtomicU64::new(0);
+
+fn clock() -> Duration {
+    Duration::from_secs(NOW_S.load(Ordering::Relaxed))
+}
+
+/// A separate clock, since tests run in parallel
+static PULSING_NOW_S: AtomicU64 = AtomicU64::new(0);
+
+fn pulsing_clock() -> Duration {
+    Duration::from_secs(PULSING_NOW_S.load(Ordering::Relaxed))
+}
+
+/// An output shared with the test, since the printer is owned by the global logger
+#[derive(Clone, Default)]
+struct Output(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Output {
+    fn text(&self) -> String {
+        String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+    }
+}
+
+/// Installs the logger once for all tests, returning the shared printer and its output.
+fn logged_printer() -> &'static (Arc<Mutex<Printer<Output>>>, Output) {
+    static PRINTER: OnceLock<(Arc<Mutex<Printer<Output>>>, Output)> = OnceLock::new();
+    PRINTER.get_or_init(|| {
+        let output = Output::default();
+        let printer = Printer::with(output.clone(), Mode::Lines).shared();
+        Logger::new(Arc::clone(&printer))
+            .install(LevelFilter::Warn)
+            .unwrap();
+        (printer, output)
+    })
+}
+
+/// Runs the given function in a thread, failing instead of hanging if it deadlocks.
+fn without_deadlock<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || sender.send(f()).unwrap());
+    receiver
+        .recv_timeout(Duration::from_secs(10))
+        .expect("Logging while printing should not deadlock.")
+}
+
+#[test]
+fn stalled_bar() {
+    let (printer, output) = logged_printer();
+    let printer = Arc::clone(printer);
+
+    let stalls = Arc::new(AtomicUsize::new(0));
+    let counted_stalls = Arc::clone(&stalls);
+
+    let (stalled, stalled_again) = without_deadlock(move || {
+        let mut progress_bar = ClampingBar::new().timed_by(clock);
+        progress_bar.set_len(20);
+        progress_bar.set_stall_timeout(Some(Duration::from_secs(60)));
+        progress_bar.set_on_stall(move |_stalled| {
+            counted_stalls.fetch_add(1, Ordering::Relaxed);
+        });
+
+        NOW_S.store(10, Ordering::Relaxed);
+        progress_bar.set(0.3);
+        printer.lock().unwrap().print(&mut progress_bar).unwrap();
+
+        NOW_S.store(200, Ordering::Relaxed);
+        printer.lock().unwrap().print(&mut progress_bar).unwrap();
+        (progress_bar.take_stall(), progress_bar.take_stall())
+    });
+
+    assert_eq!(stalled, Some(Duration::from_secs(190)));
+    assert_eq!(stalled_again, None);
+    assert_eq!(stalls.load(Ordering::Relaxed), 1);
+
+    let text = output.text();
+    assert!(text.contains("[=====>............] stalled for 3min"));
+    assert!(text.contains("[WARN] The bar has stalled, having made no progress for 3min."));
+}
+
+/// Logs a warning when rendered, e.g. like a bar detecting a problem.
+struct Warning;
+
+impl Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        warn!("Rendering a warning");
+        f.write_str("rendered")
+    }
+}
+
+#[test]
+fn logging_while_printing() {
+    let (printer, output) = logged_printer();
+    let printer = Arc::clone(printer);
+
+    without_deadlock(move || printer.lock().unwrap().println(Warning).unwrap());
+    assert!(output.text().contains("rendered"));
+}
+
+#[test]
+fn stalled_bar_pulses() {
+    let mut progress_bar = ClampingBar::new().timed_by(pulsing_clock);
+    progress_bar.set_len(20);
+    progress_bar.set_stall_timeout(Some(Duration::from_secs(60)));
+    progress_bar.inner_mut().set_effect(Effect::Gradient);
+    progress_bar.inner_mut().set_colored(true);
+
+    PULSING_NOW_S.store(10, Ordering::Relaxed);
+    progress_bar.set(0.3);
+    assert!(progress_bar.to_string().starts_with("[\x1b[38;2;255;0;0m="));
+
+    // gray while stalled
+    PULSING_NOW_S.store(200, Ordering::Relaxed);
+    progress_bar.tick();
+    assert!(progress_bar
+        .to_string()
+        .starts_with("[\x1b[38;2;101;101;101m="));
+
+    // progress ends the stall
+    progress_bar.set(0.4);
+    assert!(progress_bar.to_string().starts_with("[\x1b[38;2;255;0;0m="));
+}